@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate criterion;
+extern crate sirena_types;
+
+use criterion::{black_box, Criterion};
+use sirena_types::{AircraftCode, AirlineCode, AirportCode, CityCode};
+
+macro_rules! bench_code {
+    ($c: expr, $t: ty, $valid: expr, $invalid: expr) => {
+        $c.bench_function(concat!(stringify!($t), "::from_str valid"), |b| {
+            b.iter(|| black_box($valid).parse::<$t>())
+        });
+        $c.bench_function(concat!(stringify!($t), "::from_str invalid"), |b| {
+            b.iter(|| black_box($invalid).parse::<$t>())
+        });
+        let code: $t = $valid.parse().unwrap();
+        $c.bench_function(concat!(stringify!($t), "::as_str"), |b| {
+            b.iter(|| black_box(&code).as_str().len())
+        });
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    bench_code!(c, AircraftCode, "ПУ1", "ПУ!");
+    bench_code!(c, AirlineCode, "С7", "77");
+    bench_code!(c, AirportCode, "ШРМ", "SVO");
+    bench_code!(c, CityCode, "МОВ", "МОВЛ");
+}
+
+fn decode(c: &mut Criterion) {
+    let code: CityCode = "МОВ".parse().unwrap();
+    c.bench_function("KOI8-R decode as_str", |b| b.iter(|| black_box(&code).as_str().into_owned()));
+    c.bench_function("KOI8-R decode chars", |b| b.iter(|| black_box(&code).chars().count()));
+    c.bench_function("KOI8-R from_bytes", |b| b.iter(|| CityCode::from_bytes(black_box(code.as_bytes()))));
+}
+
+criterion_group!(benches, parse, decode);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+//! `arbitrary::Arbitrary` impls for fuzzing, generating only valid codes
+
+use core::str::FromStr;
+use alloc::string::String;
+
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use super::{AircraftCode, AirlineCode, AirportCode, CityCode, LETTERS, LETTERS_AND_DIGITS};
+
+/// Code of `len` characters drawn from `alphabet`. Type-specific rules (like
+/// airline digits) are left to `FromStr`, rejecting the input if they fail
+fn code<T: FromStr>(u: &mut Unstructured, len: usize, alphabet: &[char]) -> Result<T> {
+    let mut value = String::new();
+    for _ in 0..len {
+        value.push(*u.choose(alphabet)?);
+    }
+    value.parse().map_err(|_| Error::IncorrectFormat)
+}
+
+macro_rules! gen_arbitrary {
+    ($t: ty, $len: expr, $alphabet: expr) => {
+        impl<'a> Arbitrary<'a> for $t {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                code(u, $len, $alphabet)
+            }
+        }
+    }
+}
+
+gen_arbitrary!(AircraftCode, 3, LETTERS_AND_DIGITS);
+gen_arbitrary!(AirlineCode, 2, LETTERS_AND_DIGITS);
+gen_arbitrary!(AirportCode, 3, LETTERS);
+gen_arbitrary!(CityCode, 3, LETTERS);
+
+#[test]
+fn test_arbitrary() {
+    let bytes: alloc::vec::Vec<u8> = (0..=255).collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..20 {
+        let airport = AirportCode::arbitrary(&mut u).unwrap();
+        assert_eq!(airport.as_str().parse::<AirportCode>().unwrap(), airport);
+        let aircraft = AircraftCode::arbitrary(&mut u).unwrap();
+        assert_eq!(aircraft.as_str().parse::<AircraftCode>().unwrap(), aircraft);
+    }
+    assert_eq!(AirlineCode::arbitrary(&mut Unstructured::new(&[40, 40])), Err(Error::IncorrectFormat));
+}
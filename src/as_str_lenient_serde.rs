@@ -0,0 +1,61 @@
+//! Like `as_str_serde`, but deserialization goes through `from_str_lenient`,
+//! so surrounding whitespace and lowercase input are accepted:
+//!
+//! ```
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate sirena_types;
+//! # use sirena_types::AirportCode;
+//! #[derive(Serialize, Deserialize)]
+//! struct Segment {
+//!     #[serde(with = "sirena_types::as_str_lenient_serde")]
+//!     from: AirportCode,
+//! }
+//! # fn main() {}
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor};
+
+use super::SirenaCode;
+
+pub use super::as_str_serde::serialize;
+
+pub fn deserialize<'de, C: SirenaCode, D: Deserializer<'de>>(deserializer: D) -> Result<C, D::Error> {
+    struct StrVisitor<C>(PhantomData<C>);
+
+    impl<'de, C: SirenaCode> Visitor<'de> for StrVisitor<C> {
+        type Value = C;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a code as a string")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<C, E> {
+            C::from_str_lenient(value).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor(PhantomData))
+}
+
+#[test]
+fn test_as_str_lenient_serde() {
+    use serde_json;
+    use super::{AirportCode, CurrencyCode};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Fare {
+        #[serde(with = "self")]
+        from: AirportCode,
+        #[serde(with = "self")]
+        currency: CurrencyCode,
+    }
+
+    let fare: Fare = serde_json::from_str(r#"{"from": " внк ", "currency": "eur"}"#).unwrap();
+    assert_eq!(fare.from, "ВНК");
+    assert_eq!(fare.currency, "EUR");
+    assert_eq!(serde_json::to_string(&fare).unwrap(), r#"{"from":"ВНК","currency":"EUR"}"#);
+    assert!(serde_json::from_str::<Fare>(r#"{"from": "SVO", "currency": "eur"}"#).is_err());
+}
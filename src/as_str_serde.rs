@@ -0,0 +1,63 @@
+//! Serialize a code as its decoded string in every format, binary ones included:
+//!
+//! ```
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate sirena_types;
+//! # use sirena_types::AirportCode;
+//! #[derive(Serialize, Deserialize)]
+//! struct Segment {
+//!     #[serde(with = "sirena_types::as_str_serde")]
+//!     from: AirportCode,
+//! }
+//! # fn main() {}
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Serializer;
+
+use super::SirenaCode;
+
+pub fn serialize<C: SirenaCode, S: Serializer>(code: &C, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&code.as_str())
+}
+
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+    where C: SirenaCode + FromStr, C::Err: fmt::Display, D: Deserializer<'de>
+{
+    struct StrVisitor<C>(PhantomData<C>);
+
+    impl<'de, C> Visitor<'de> for StrVisitor<C> where C: FromStr, C::Err: fmt::Display {
+        type Value = C;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a code as a string")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<C, E> {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor(PhantomData))
+}
+
+#[test]
+fn test_as_str_serde() {
+    use bincode;
+    use super::AirportCode;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Segment {
+        #[serde(with = "self")]
+        from: AirportCode,
+    }
+
+    let segment = Segment { from: "ВНК".parse().unwrap() };
+    let bytes = bincode::serialize(&segment).unwrap();
+    assert_eq!(&bytes[8..], "ВНК".as_bytes());
+    assert_eq!(bincode::deserialize::<Segment>(&bytes).unwrap(), segment);
+}
@@ -0,0 +1,122 @@
+//! Day and month with a Cyrillic month abbreviation, e.g. 15ЯНВ.
+//! The year is not part of the Sirena form and has to be supplied.
+
+use core::fmt;
+use core::str::FromStr;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use chrono::{Datelike, NaiveDate};
+
+use super::normalize;
+
+/// Month abbreviations as Sirena writes them in dates, January first
+pub static MONTHS: [&str; 12] = [
+    "ЯНВ", "ФЕВ", "МАР", "АПР", "МАЙ", "ИЮН", "ИЮЛ", "АВГ", "СЕН", "ОКТ", "НОЯ", "ДЕК",
+];
+
+/// Longest month, February has 29 days as the year is unknown
+const DAYS_IN_MONTH: [u8; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct SirenaDate {
+    month: u8,
+    day: u8,
+}
+
+impl SirenaDate {
+    /// Day of the month, starting with 1
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Month, 1 for January
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Date in `year`, `None` for 29ФЕВ in a non-leap year
+    pub fn with_year(&self, year: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, self.month.into(), self.day.into())
+    }
+}
+
+impl From<NaiveDate> for SirenaDate {
+    fn from(date: NaiveDate) -> Self {
+        SirenaDate { month: date.month() as u8, day: date.day() as u8 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SirenaDateParseError {
+    InvalidLength(usize),
+    InvalidDigit(char),
+    UnknownMonth,
+    DayOutOfRange(u8),
+}
+
+impl fmt::Display for SirenaDateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SirenaDateParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 4 or 5", len),
+            SirenaDateParseError::InvalidDigit(c) => write!(f, "invalid character {}, expected [0-9]", c),
+            SirenaDateParseError::UnknownMonth => write!(f, "unknown month, expected one of ЯНВ-ДЕК"),
+            SirenaDateParseError::DayOutOfRange(day) => write!(f, "day {} out of range for the month", day),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SirenaDateParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl FromStr for SirenaDate {
+    type Err = SirenaDateParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let len = value.chars().count();
+        if len != 4 && len != 5 {
+            return Err(SirenaDateParseError::InvalidLength(len));
+        }
+        let split = value.char_indices().nth(len - 3).map_or(value.len(), |(i, _)| i);
+        let (day, month) = value.split_at(split);
+        if let Some(c) = day.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(SirenaDateParseError::InvalidDigit(c));
+        }
+        let month: String = month.chars().map(normalize).collect();
+        let month = MONTHS.iter().position(|&m| m == month).ok_or(SirenaDateParseError::UnknownMonth)?;
+        let day = day.bytes().fold(0, |day, b| day * 10 + (b - b'0'));
+        if day == 0 || day > DAYS_IN_MONTH[month] {
+            return Err(SirenaDateParseError::DayOutOfRange(day));
+        }
+        Ok(SirenaDate { month: month as u8 + 1, day })
+    }
+}
+
+impl fmt::Display for SirenaDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}{}", self.day, MONTHS[usize::from(self.month) - 1])
+    }
+}
+
+#[test]
+fn test_sirena_date() {
+    let date: SirenaDate = "15янв".parse().unwrap();
+    assert_eq!((date.day(), date.month()), (15, 1));
+    assert_eq!(date.to_string(), "15ЯНВ");
+    assert_eq!(date.with_year(2024), NaiveDate::from_ymd_opt(2024, 1, 15));
+    assert_eq!("5МАЙ".parse::<SirenaDate>().unwrap().to_string(), "05МАЙ");
+    assert_eq!(SirenaDate::from(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()).to_string(), "31ДЕК");
+    let leap: SirenaDate = "29ФЕВ".parse().unwrap();
+    assert!(leap.with_year(2024).is_some() && leap.with_year(2023).is_none());
+    assert_eq!("30ФЕВ".parse::<SirenaDate>(), Err(SirenaDateParseError::DayOutOfRange(30)));
+    assert_eq!("00ЯНВ".parse::<SirenaDate>(), Err(SirenaDateParseError::DayOutOfRange(0)));
+    assert_eq!("15JAN".parse::<SirenaDate>(), Err(SirenaDateParseError::UnknownMonth));
+    assert_eq!("1ХЯНВ".parse::<SirenaDate>(), Err(SirenaDateParseError::InvalidDigit('Х')));
+    assert_eq!("2024ЯНВ".parse::<SirenaDate>(), Err(SirenaDateParseError::InvalidLength(7)));
+}
@@ -0,0 +1,127 @@
+//! Flight number, airline code followed by the number, e.g. СУ1234
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::{AirlineCode, ParseError};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct FlightNumber {
+    airline: AirlineCode,
+    number: u16,
+    // KOI8-R of the airline and the unpadded number, for `as_bytes`
+    bytes: [u8; 6],
+    len: u8,
+}
+
+impl FlightNumber {
+    pub fn airline(&self) -> AirlineCode {
+        self.airline
+    }
+
+    pub fn number(&self) -> u16 {
+        self.number
+    }
+
+    /// KOI8-R bytes of the combined form, as displayed
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlightNumberParseError {
+    Airline(ParseError),
+    MissingNumber,
+    InvalidDigit(char),
+    NumberTooLarge,
+    ZeroNumber,
+}
+
+impl fmt::Display for FlightNumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FlightNumberParseError::Airline(ref e) => write!(f, "invalid airline code: {}", e),
+            FlightNumberParseError::MissingNumber => write!(f, "missing flight number"),
+            FlightNumberParseError::InvalidDigit(c) => write!(f, "invalid character {}, expected [0-9]", c),
+            FlightNumberParseError::NumberTooLarge => write!(f, "flight number too large, expected at most 9999"),
+            FlightNumberParseError::ZeroNumber => write!(f, "flight number 0, expected 1 to 9999"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FlightNumberParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            FlightNumberParseError::Airline(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for FlightNumber {
+    type Err = FlightNumberParseError;
+
+    /// Leading zeros are dropped, so СУ0012 is the same flight as СУ12
+    /// and is displayed as such
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let split = value.char_indices().nth(2).map_or(value.len(), |(i, _)| i);
+        let (airline, number) = value.split_at(split);
+        let airline: AirlineCode = airline.parse().map_err(FlightNumberParseError::Airline)?;
+        if number.is_empty() {
+            return Err(FlightNumberParseError::MissingNumber);
+        }
+        if let Some(c) = number.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(FlightNumberParseError::InvalidDigit(c));
+        }
+        if number.len() > 4 {
+            return Err(FlightNumberParseError::NumberTooLarge);
+        }
+        let number: u16 = number.parse().map_err(|_| FlightNumberParseError::NumberTooLarge)?;
+        if number == 0 {
+            return Err(FlightNumberParseError::ZeroNumber);
+        }
+        let mut bytes = [0; 6];
+        bytes[..2].copy_from_slice(airline.as_bytes());
+        let mut len = 2;
+        let mut divisor = 1000;
+        while divisor > 0 {
+            if number >= divisor {
+                bytes[len] = b'0' + (number / divisor % 10) as u8;
+                len += 1;
+            }
+            divisor /= 10;
+        }
+        Ok(FlightNumber { airline, number, bytes, len: len as u8 })
+    }
+}
+
+impl fmt::Display for FlightNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.airline, self.number)
+    }
+}
+
+#[test]
+fn test_flight_number() {
+    let flight: FlightNumber = "СУ1234".parse().unwrap();
+    assert_eq!(flight.airline(), "СУ");
+    assert_eq!(flight.number(), 1234);
+    assert_eq!(flight.to_string(), "СУ1234");
+    assert_eq!(flight.as_bytes().len(), 6);
+    assert_eq!(&flight.as_bytes()[2..], b"1234");
+    let padded: FlightNumber = "СУ0012".parse().unwrap();
+    assert_eq!(padded.number(), 12);
+    assert_eq!(padded.to_string(), "СУ12");
+    assert_eq!(padded.as_bytes(), "СУ12".parse::<FlightNumber>().unwrap().as_bytes());
+    assert_eq!(padded, "СУ12".parse().unwrap());
+    assert_eq!("СУ0".parse::<FlightNumber>(), Err(FlightNumberParseError::ZeroNumber));
+    assert_eq!("СУ0000".parse::<FlightNumber>(), Err(FlightNumberParseError::ZeroNumber));
+    assert_eq!("СУ".parse::<FlightNumber>(), Err(FlightNumberParseError::MissingNumber));
+    assert_eq!("СУ12345".parse::<FlightNumber>(), Err(FlightNumberParseError::NumberTooLarge));
+    assert_eq!("СУ12А".parse::<FlightNumber>(), Err(FlightNumberParseError::InvalidDigit('А')));
+}
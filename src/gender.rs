@@ -0,0 +1,89 @@
+//! Passenger gender, М or Ж
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::normalize;
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum Gender {
+    /// М, мужской
+    Male,
+    /// Ж, женский
+    Female,
+}
+
+impl Gender {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Gender::Male => "М",
+            Gender::Female => "Ж",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GenderParseError {
+    InvalidLength(usize),
+    Unrecognized(char),
+}
+
+impl fmt::Display for GenderParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GenderParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 1", len),
+            GenderParseError::Unrecognized(c) => write!(f, "invalid character {}, expected one of МЖMF", c),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GenderParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl FromStr for Gender {
+    type Err = GenderParseError;
+
+    /// Accepts Cyrillic М/Ж and Latin M/F, in any case
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut chars = value.chars();
+        let c = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(GenderParseError::InvalidLength(value.chars().count())),
+        };
+        match normalize(c).to_ascii_uppercase() {
+            'М' | 'M' => Ok(Gender::Male),
+            'Ж' | 'F' => Ok(Gender::Female),
+            _ => Err(GenderParseError::Unrecognized(c)),
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+gen_str_serde!(Gender, "a gender code");
+gen_str_schema!(Gender, 1, 1, "^[МЖ]$");
+
+#[test]
+fn test_gender() {
+    use serde_json;
+
+    assert_eq!("М".parse::<Gender>().unwrap(), Gender::Male);
+    assert_eq!("ж".parse::<Gender>().unwrap(), Gender::Female);
+    assert_eq!("f".parse::<Gender>().unwrap().to_string(), "Ж");
+    assert_eq!("M".parse::<Gender>().unwrap(), Gender::Male);
+    assert_eq!("Х".parse::<Gender>(), Err(GenderParseError::Unrecognized('Х')));
+    assert_eq!("МЖ".parse::<Gender>(), Err(GenderParseError::InvalidLength(2)));
+    assert_eq!(serde_json::to_string(&Gender::Female).unwrap(), "\"Ж\"");
+    assert_eq!(serde_json::from_str::<Gender>("\"М\"").unwrap(), Gender::Male);
+}
@@ -0,0 +1,210 @@
+//! Short codes of varying length, stored inline as KOI8-R bytes
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use encoding_rs::KOI8_R;
+
+use super::{is_letter_or_latin, normalize, ParseError};
+
+/// 1 to N KOI8-R bytes, the unused tail is always zero so `Eq` and `Hash`
+/// can be derived. `Ord` is on the used bytes, so "B" sorts after "AA"
+/// as with the fixed-length codes rather than by length first
+#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+struct InlineCode<const N: usize> {
+    len: u8,
+    bytes: [u8; N],
+}
+
+impl<const N: usize> Ord for InlineCode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<const N: usize> PartialOrd for InlineCode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> InlineCode<N> {
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..usize::from(self.len)]
+    }
+
+    /// Decoded on every call, also with `utf8-storage`. Borrowed if all ASCII
+    fn as_str(&self) -> Cow<'_, str> {
+        let (s, _) = KOI8_R.decode_without_bom_handling(self.as_bytes());
+        s
+    }
+
+    /// Normalize, check length and `is_valid` on every character, then encode to KOI8-R.
+    /// Too long input fails with `TooLong`, N being the maximum
+    fn parse(value: &str, is_valid: fn(char) -> bool) -> Result<Self, ParseError> {
+        if value.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let len = value.chars().count();
+        if len > N {
+            return Err(ParseError::TooLong { got: len, max: N });
+        }
+        let value: String = value.chars().map(normalize).collect();
+        if let Some((index, ch)) = value.chars().enumerate().find(|&(_, c)| !is_valid(c)) {
+            return Err(ParseError::InvalidLetter { ch, index });
+        }
+        // every valid character is a single KOI8-R byte
+        let (koi8str, _, _) = KOI8_R.encode(&value);
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&koi8str);
+        Ok(InlineCode { len: len as u8, bytes })
+    }
+}
+
+fn is_alphanumeric(c: char) -> bool {
+    is_letter_or_latin(c) || c.is_ascii_digit()
+}
+
+macro_rules! gen_inline_code {
+    ($t: ident, $max: expr, $is_valid: expr, $pattern: expr) => {
+        impl $t {
+            /// Longest code accepted, in characters
+            pub const MAX_LEN: usize = $max;
+
+            pub fn as_str(&self) -> Cow<'_, str> {
+                self.0.as_str()
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                self.0.as_bytes()
+            }
+
+            /// Length in characters, from 1 to `MAX_LEN`
+            pub fn len(&self) -> usize {
+                self.as_bytes().len()
+            }
+
+            /// Always false, empty codes are rejected by `from_str`
+            pub fn is_empty(&self) -> bool {
+                false
+            }
+        }
+
+        impl FromStr for $t {
+            type Err = ParseError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok($t(InlineCode::parse(value, $is_valid)?))
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.pad(&self.as_str())
+            }
+        }
+
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+
+        gen_str_serde!($t, concat!("a ", stringify!($t), " string"));
+
+        gen_str_schema!($t, 1, $max, $pattern);
+    }
+}
+
+/// Airport terminal, 1 or 2 Cyrillic or Latin letters or digits, like 1, B or Д
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct TerminalCode(InlineCode<2>);
+
+gen_inline_code!(TerminalCode, 2, is_alphanumeric, "^[А-ЯA-Z0-9]{1,2}$");
+
+/// Boarding gate, 1 to 4 Cyrillic or Latin letters or digits, like 12 or A15Б
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct GateCode(InlineCode<4>);
+
+gen_inline_code!(GateCode, 4, is_alphanumeric, "^[А-ЯA-Z0-9]{1,4}$");
+
+/// Fare basis, 1 to 15 Cyrillic or Latin letters or digits, like YRT or BLXOW
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct FareBasis(InlineCode<15>);
+
+gen_inline_code!(FareBasis, 15, is_alphanumeric, "^[А-ЯA-Z0-9]{1,15}$");
+
+#[test]
+fn test_terminal_code() {
+    let terminal: TerminalCode = "д".parse().unwrap();
+    assert_eq!(terminal.to_string(), "Д");
+    assert_eq!(terminal.as_bytes(), b"\xe4");
+    assert_eq!("1".parse::<TerminalCode>().unwrap().len(), 1);
+    assert_eq!("".parse::<TerminalCode>(), Err(ParseError::Empty));
+    assert_eq!("ABC".parse::<TerminalCode>(), Err(ParseError::TooLong { got: 3, max: 2 }));
+    // lexicographic like the fixed-length codes, not by length first
+    assert!("B".parse::<TerminalCode>().unwrap() > "AA".parse::<TerminalCode>().unwrap());
+    assert!("1".parse::<GateCode>().unwrap() < "12".parse::<GateCode>().unwrap());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_inline_json_schema() {
+    use serde_json;
+
+    let schema = serde_json::to_value(schemars::schema_for!(GateCode)).unwrap();
+    assert_eq!(schema["minLength"], 1);
+    assert_eq!(schema["maxLength"], 4);
+    assert_eq!(schema["pattern"], "^[А-ЯA-Z0-9]{1,4}$");
+}
+
+#[test]
+fn test_gate_code() {
+    use serde_json;
+
+    let gate: GateCode = "A15Б".parse().unwrap();
+    assert_eq!(gate.as_str(), "A15Б");
+    assert_eq!(gate.len(), 4);
+    assert!("12".parse::<GateCode>().unwrap() < "13".parse::<GateCode>().unwrap());
+    assert_eq!("1-2".parse::<GateCode>(), Err(ParseError::InvalidLetter { ch: '-', index: 1 }));
+    assert_eq!(serde_json::to_string(&gate).unwrap(), "\"A15Б\"");
+    assert_eq!(serde_json::from_str::<GateCode>("\"A15б\"").unwrap(), "A15Б".parse::<GateCode>().unwrap());
+}
+
+#[test]
+fn test_fare_basis() {
+    let fare: FareBasis = "BLXOW".parse().unwrap();
+    assert_eq!(fare.as_str(), "BLXOW");
+    assert_eq!(fare.len(), 5);
+    assert_eq!("ЭКОНОМ1".parse::<FareBasis>().unwrap().as_bytes().len(), 7);
+    assert_eq!(FareBasis::MAX_LEN, 15);
+    assert!("Y".repeat(15).parse::<FareBasis>().is_ok());
+    assert_eq!("Y".repeat(16).parse::<FareBasis>(), Err(ParseError::TooLong { got: 16, max: 15 }));
+    assert_eq!(format!("{:<6}|", fare), "BLXOW |");
+    let mut fares = ["Z", "YRT", "AA", "Y"].map(|f| f.parse::<FareBasis>().unwrap());
+    fares.sort();
+    assert_eq!(fares.map(|f| f.to_string()), ["AA", "Y", "YRT", "Z"]);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_fare_basis_json_schema() {
+    use serde_json;
+
+    let schema = serde_json::to_value(schemars::schema_for!(FareBasis)).unwrap();
+    assert_eq!(schema["maxLength"], 15);
+    assert_eq!(schema["pattern"], "^[А-ЯA-Z0-9]{1,15}$");
+}
+
+#[cfg(feature = "utf8-storage")]
+#[test]
+fn test_inline_not_utf8_stored() {
+    // utf8-storage only covers the fixed-length codes
+    assert!(matches!("A15Б".parse::<GateCode>().unwrap().as_str(), Cow::Owned(_)));
+    assert!(matches!("BLXOW".parse::<FareBasis>().unwrap().as_str(), Cow::Borrowed("BLXOW")));
+}
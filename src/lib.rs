@@ -1,291 +1,2549 @@
 //! https://ru.wikipedia.org/wiki/Сирена_(сеть)
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs `alloc`.
+//! The tests run in that configuration too with `cargo test --no-default-features --lib`.
+//!
+//! With serde, codes are a single scalar: a string in human-readable formats
+//! and the raw KOI8-R bytes otherwise. A code field needs no attributes, and
+//! structs of codes can be `#[serde(flatten)]`ed or wrapped in
+//! `#[serde(transparent)]` newtypes.
+//!
+//! Codes are stored as KOI8-R, the form Sirena itself uses, and `as_str`
+//! decodes them. The `utf8-storage` feature also keeps the decoded UTF-8 in
+//! every fixed-length code, like `AirportCode` or `RecordLocator`, so their
+//! `as_str` always borrows. The API doesn't change: `as_bytes` still returns
+//! the KOI8-R bytes without any encoding. Those codes grow by 19 bytes each
+//! and building one, including in `const` context, decodes it up front, so
+//! this only pays off if `as_str` is called often. The variable-length
+//! `TerminalCode`, `GateCode` and `FareBasis` aren't affected: their `as_str`
+//! still decodes, allocating unless the code is all ASCII.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "std")]
+extern crate core;
+extern crate alloc;
+// the test harness links std anyway, tests use it for HashMap and friends
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
 extern crate serde;
+extern crate encoding_rs;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "proptest")]
+#[cfg_attr(test, macro_use)]
+extern crate proptest;
+
+#[cfg(test)]
 #[macro_use]
 extern crate serde_derive;
-extern crate encoding_rs;
+#[cfg(test)]
+extern crate serde_json;
+#[cfg(test)]
+extern crate bincode;
 
-use std::fmt;
-use std::str;
-use std::str::FromStr;
-use std::borrow::Cow;
+use core::fmt;
+use core::str;
+use core::str::FromStr;
+use core::convert::TryFrom;
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::cmp::Ordering;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(test)]
+use alloc::string::ToString;
 
 use encoding_rs::KOI8_R;
 
+/// `Serialize` as `as_str` and `Deserialize` through `FromStr`, for the
+/// types that only have a string form. Defined before the modules using it
+macro_rules! gen_str_serde {
+    ($t: ty, $expecting: expr) => {
+        impl serde::Serialize for $t {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct StrVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for StrVisitor {
+                    type Value = $t;
+
+                    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<$t, E> {
+                        value.parse().map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_str(StrVisitor)
+            }
+        }
+    }
+}
+
+/// String `JsonSchema` of codes of `$min` to `$max` characters matching `$pattern`
+macro_rules! gen_str_schema {
+    ($t: ident, $min: expr, $max: expr, $pattern: expr) => {
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $t {
+            fn schema_name() -> alloc::borrow::Cow<'static, str> {
+                alloc::borrow::Cow::Borrowed(stringify!($t))
+            }
+
+            fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                schemars::json_schema!({
+                    "type": "string",
+                    "minLength": $min,
+                    "maxLength": $max,
+                    "pattern": $pattern,
+                })
+            }
+        }
+    }
+}
+
+mod tables;
+mod flight;
+mod route;
+mod passenger;
+mod gender;
+mod status;
+mod owned;
+mod ticket;
+mod seat;
+mod time;
+mod inline;
+#[cfg(feature = "chrono")]
+mod date;
+pub mod as_str_serde;
+pub mod as_str_lenient_serde;
+
+pub use flight::{FlightNumber, FlightNumberParseError};
+pub use route::Route;
+pub use passenger::PassengerType;
+pub use gender::{Gender, GenderParseError};
+pub use status::SegmentStatus;
+pub use owned::{OwnedCode, CodeKind};
+pub use ticket::{TicketNumber, TicketNumberParseError};
+pub use seat::{SeatNumber, SeatNumberParseError};
+pub use time::{SirenaTime, SirenaTimeParseError};
+pub use inline::{TerminalCode, GateCode, FareBasis};
+#[cfg(feature = "chrono")]
+pub use date::{SirenaDate, SirenaDateParseError, MONTHS};
+
+#[cfg(feature = "proptest")]
+mod proptest_impls;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+
 macro_rules! gen_display {
     ($t: ty) => {
         impl fmt::Display for $t {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{}", self.as_str())
+                fmt::Display::fmt(&self.0, f)
             }
         }
 
-	impl fmt::Debug for $t {
+        gen_display!(@debug $t);
+    };
+    // `{:#}` prints the Latin IATA code when `to_latin` knows one
+    ($t: ty, latin) => {
+        impl fmt::Display for $t {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{}", self.as_str())
+                match self.to_latin() {
+                    Some(latin) if f.alternate() => f.pad(latin),
+                    _ => fmt::Display::fmt(&self.0, f),
+                }
             }
         }
-    }
+
+        gen_display!(@debug $t);
+    };
+    (@debug $t: ty) => {
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
 }
 
 macro_rules! gen_as {
     () => {
-        pub fn as_str(&self) -> Cow<str> {
-            let (s, _, _) = KOI8_R.decode(&self.0);
-            s
+        pub fn as_str(&self) -> Cow<'_, str> {
+            self.0.as_str()
         }
 
         pub fn as_bytes(&self) -> &[u8] {
-            &self.0
+            self.0.as_bytes()
+        }
+
+        /// Like `as_str`, but first checks the stored bytes are valid for the type
+        /// and in the uppercase form `from_str` stores, see `from_bytes`
+        pub fn try_as_str(&self) -> Result<Cow<'_, str>, DecodeError> {
+            Self::from_bytes(self.as_bytes()).map_err(|error| DecodeError { error })?;
+            Ok(self.as_str())
+        }
+
+        /// Pass the decoded code to `f` without allocating, unlike `as_str`
+        pub fn with_str<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+            self.0.with_str(f)
+        }
+
+        /// Parse user input: trims surrounding whitespace (Unicode-aware) and
+        /// uppercases Latin letters before validating. Lowercase Cyrillic and
+        /// Ё are already normalized by `from_str`, which is otherwise exact.
+        /// If that fails, Latin look-alikes such as A or M are replaced with
+        /// their Cyrillic twins and parsing is retried.
+        pub fn from_str_lenient(value: &str) -> Result<Self, ParseError> {
+            let value: String = value.trim().chars().map(|c| c.to_ascii_uppercase()).collect();
+            value.parse().or_else(|e| {
+                let cyrillic: String = value.chars().map(|c| latin_confusable(c).unwrap_or(c)).collect();
+                cyrillic.parse().map_err(|_| e)
+            })
+        }
+
+        /// Parse a code typed with the keyboard left on the Latin layout, so
+        /// IHV is read as ШРМ. Keys are mapped by position, unlike `to_latin`
+        /// or `from_str_lenient`; digits and other characters are kept as is.
+        /// Latin codes such as currencies can't go through this.
+        pub fn from_translit(value: &str) -> Result<Self, ParseError> {
+            let value: String = value.chars().map(|c| keyboard_cyrillic(c).unwrap_or(c)).collect();
+            value.parse()
+        }
+
+        /// Validate `value` like `from_str` does and write its KOI8-R bytes
+        /// into the beginning of `out`, returning the number of bytes written
+        pub fn encode_into(value: &str, out: &mut [u8]) -> Result<usize, ParseError> {
+            let code: Self = value.parse()?;
+            let got = out.len();
+            let out = out.get_mut(..Self::LEN).ok_or(ParseError::BufferTooSmall { needed: Self::LEN, got })?;
+            out.copy_from_slice(code.as_bytes());
+            Ok(Self::LEN)
+        }
+
+        /// Like `from_str`, but the error keeps a copy of the input for logging
+        pub fn from_str_verbose(value: &str) -> Result<Self, ParseErrorOwned> {
+            value.parse().map_err(|error| ParseErrorOwned {
+                input: value.into(),
+                type_name: <Self as SirenaCode>::NAME,
+                error,
+            })
+        }
+
+        /// Per-letter ASCII transliteration, e.g. ШРМ is SHRM. Unlike `to_latin`
+        /// this is not an IATA code, but every code has one
+        pub fn to_ascii(&self) -> String {
+            let mut ascii = String::new();
+            for c in self.chars() {
+                transliterate(c, &mut ascii);
+            }
+            ascii
+        }
+
+        /// Decoded characters, one per KOI8-R byte, without allocating
+        pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+            self.0.chars()
+        }
+
+        /// Copy of the code with the character at `index` replaced,
+        /// validated like `from_str` does
+        pub fn with_char(&self, index: usize, c: char) -> Result<Self, ParseError> {
+            if index >= Self::LEN {
+                return Err(ParseError::IndexOutOfRange { index, len: Self::LEN });
+            }
+            let value: String = self.chars().enumerate().map(|(i, old)| if i == index { c } else { old }).collect();
+            value.parse()
+        }
+
+        /// Compare in Cyrillic alphabet order. The derived `Ord` compares
+        /// KOI8-R bytes, which puts e.g. Б before А
+        pub fn cmp_alpha(&self, other: &Self) -> Ordering {
+            self.chars().cmp(other.chars())
+        }
+
+        /// Write the KOI8-R bytes of the code into `w`
+        #[cfg(feature = "std")]
+        pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+            w.write_all(self.as_bytes())
+        }
+
+        /// Read exactly `LEN` bytes from `r` and validate them like `from_bytes` does
+        #[cfg(feature = "std")]
+        pub fn from_reader<R: std::io::Read>(r: &mut R) -> std::io::Result<Result<Self, ParseError>> {
+            let mut bytes = [0; Self::LEN];
+            r.read_exact(&mut bytes)?;
+            Ok(Self::from_bytes(&bytes))
         }
     }
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Hash, Clone, Copy)]
-pub struct AircraftCode([u8; 3]);
+macro_rules! gen_as_ref {
+    ($t: ty) => {
+        impl AsRef<[u8]> for $t {
+            fn as_ref(&self) -> &[u8] {
+                self.as_bytes()
+            }
+        }
 
-gen_display!(AircraftCode);
-impl AircraftCode {
-    gen_as!();
-}
+        /// Iterate the KOI8-R bytes
+        impl<'a> IntoIterator for &'a $t {
+            type Item = u8;
+            type IntoIter = core::iter::Copied<core::slice::Iter<'a, u8>>;
 
-#[derive(Debug)]
-pub enum AircraftCodeParseError {
-    InvalidLength(usize),
-    InvalidLetter(char),
+            fn into_iter(self) -> Self::IntoIter {
+                self.as_bytes().iter().copied()
+            }
+        }
+    }
 }
 
-impl fmt::Display for AircraftCodeParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            AircraftCodeParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 3", len),
-            AircraftCodeParseError::InvalidLetter(c) => write!(f, "invalid character {}, expected [А-Я]", c),
+/// `Hash`, `Eq` and `Ord` all work on the KOI8-R bytes, so maps keyed
+/// by codes can be queried with a `&[u8]`.
+/// `Hash` is that of the `[u8]` slice of `as_bytes()` and doesn't depend
+/// on the storage layout, so hashes persisted with a fixed hasher stay valid
+macro_rules! gen_borrow {
+    ($t: ty) => {
+        impl Borrow<[u8]> for $t {
+            fn borrow(&self) -> &[u8] {
+                self.as_bytes()
+            }
+        }
+
+        impl Hash for $t {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.as_bytes().hash(state)
+            }
         }
     }
 }
 
-impl std::error::Error for AircraftCodeParseError {
-    fn description(&self) -> &str {
-        "aircraft code parse error"
+macro_rules! gen_into_string {
+    ($t: ty) => {
+        impl From<$t> for String {
+            fn from(code: $t) -> String {
+                code.as_str().into_owned()
+            }
+        }
     }
 }
 
-impl FromStr for AircraftCode {
-    type Err = AircraftCodeParseError;
+macro_rules! gen_partial_eq {
+    ($t: ty) => {
+        impl PartialEq<str> for $t {
+            fn eq(&self, other: &str) -> bool {
+                self.0.eq_str(other)
+            }
+        }
+
+        impl<'a> PartialEq<&'a str> for $t {
+            fn eq(&self, other: &&'a str) -> bool {
+                self.0.eq_str(other)
+            }
+        }
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.chars().count() != 3 {
-            return Err(AircraftCodeParseError::InvalidLength(value.len()));
+        impl PartialEq<$t> for str {
+            fn eq(&self, other: &$t) -> bool {
+                other.0.eq_str(self)
+            }
         }
-        for c in value.chars() {
-            if c.is_ascii_digit() || (c >= 'А' && c <= 'Я') {
-                continue;
-            } else {
-                return Err(AircraftCodeParseError::InvalidLetter(c));
+
+        impl<'a> PartialEq<$t> for &'a str {
+            fn eq(&self, other: &$t) -> bool {
+                other.0.eq_str(self)
             }
         }
-        let (koi8str, _, _) = KOI8_R.encode(value);
-        let mut bytes = [0; 3];
-        bytes.copy_from_slice(&koi8str);
-        Ok(AircraftCode(bytes))
     }
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Hash, Clone, Copy)]
-pub struct AirlineCode([u8; 2]);
+macro_rules! gen_try_from {
+    ($t: ty) => {
+        impl<'a> TryFrom<&'a [u8]> for $t {
+            type Error = ParseError;
 
-gen_display!(AirlineCode);
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                <$t>::from_bytes(bytes)
+            }
+        }
 
-impl AirlineCode {
-    gen_as!();
+        impl<'a> TryFrom<&'a str> for $t {
+            type Error = ParseError;
 
-    /// Reconstruct AirlineCode from AirlineCode.as_bytes()
-    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
-        let mut mine = [0; 2];
+            fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
 
-        mine.copy_from_slice(bytes);
-        AirlineCode(mine)
+        impl TryFrom<String> for $t {
+            type Error = ParseError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
     }
 }
 
-#[derive(Debug)]
-pub enum AirlineCodeParseError {
-    InvalidLength(usize),
-    InvalidLetter(char),
-    TooManyDigits(u32),
+/// Human-readable formats (JSON, YAML...) get the decoded string,
+/// binary ones keep the compact KOI8-R bytes
+macro_rules! gen_serialize {
+    ($t: ident) => {
+        impl serde::Serialize for $t {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&self.as_str())
+                } else {
+                    serializer.serialize_newtype_struct(stringify!($t), &(self.0).0)
+                }
+            }
+        }
+    }
 }
 
+/// Accepts a string (validated with `FromStr`) as well as KOI8-R bytes,
+/// so both the human-readable and the binary forms round-trip
+macro_rules! gen_deserialize {
+    ($t: ident, $len: expr) => {
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                use serde::de::{self, Deserialize, SeqAccess};
 
-impl fmt::Display for AirlineCodeParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            AirlineCodeParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 2", len),
-            AirlineCodeParseError::InvalidLetter(c) => write!(f, "invalid character {}, expected [А-Я]", c),
-            AirlineCodeParseError::TooManyDigits(digits) => write!(f, "got {} digits, only 1 allowed", digits),
+                struct CodeVisitor;
+
+                impl<'de> de::Visitor<'de> for CodeVisitor {
+                    type Value = $t;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        write!(f, "{} as a string or {} KOI8-R bytes", stringify!($t), $len)
+                    }
+
+                    fn visit_str<E: de::Error>(self, value: &str) -> Result<$t, E> {
+                        value.parse().map_err(E::custom)
+                    }
+
+                    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<$t, E> {
+                        <$t>::from_bytes(value).map_err(E::custom)
+                    }
+
+                    fn visit_newtype_struct<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<$t, D::Error> {
+                        let bytes = <[u8; $len]>::deserialize(deserializer)?;
+                        <$t>::from_bytes(&bytes).map_err(de::Error::custom)
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$t, A::Error> {
+                        let mut bytes = [0; $len];
+                        for (i, b) in bytes.iter_mut().enumerate() {
+                            *b = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                        }
+                        <$t>::from_bytes(&bytes).map_err(de::Error::custom)
+                    }
+                }
+
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(CodeVisitor)
+                } else {
+                    deserializer.deserialize_newtype_struct(stringify!($t), CodeVisitor)
+                }
+            }
         }
     }
 }
 
-impl std::error::Error for AirlineCodeParseError {
-    fn description(&self) -> &str {
-        "airline code parse error"
+macro_rules! gen_len {
+    ($t: ident, $len: expr) => {
+        impl $t {
+            /// Length of the code in KOI8-R bytes
+            pub const LEN: usize = $len;
+
+            pub fn len(&self) -> usize {
+                $len
+            }
+
+            /// Storage length in KOI8-R bytes, not the UTF-8 length of `as_str`
+            pub fn byte_len(&self) -> usize {
+                self.as_bytes().len()
+            }
+
+            /// Number of decoded characters, equal to `byte_len` as KOI8-R is single-byte
+            pub fn char_len(&self) -> usize {
+                self.chars().count()
+            }
+
+            /// Codes have a fixed length, so this is always false
+            pub fn is_empty(&self) -> bool {
+                false
+            }
+        }
     }
 }
 
-impl FromStr for AirlineCode {
-    type Err = AirlineCodeParseError;
+macro_rules! gen_default {
+    ($t: ident) => {
+        /// "Unset" code: all spaces, which decodes to a blank string and is never a valid code.
+        /// It serializes, but as it isn't valid it fails to deserialize, in any
+        /// format. Structs that are persisted should use `Option<_>` for unset codes
+        impl Default for $t {
+            fn default() -> Self {
+                $t(CyrillicCode::default())
+            }
+        }
+    }
+}
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.chars().count() != 2 {
-            return Err(AirlineCodeParseError::InvalidLength(value.len()));
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __code_literal {
+    ($t: ident, $len: expr, $max_digits: expr, $latin: expr, $code: expr) => {{
+        // a const item forces compile-time evaluation even outside of const context
+        const CODE: $crate::$t = $crate::$t::from_bytes_array($crate::encode_literal::<$len>($code, $max_digits, $latin));
+        CODE
+    }}
+}
+
+/// Compile-time checked `AircraftCode` literal
+///
+/// ```
+/// # #[macro_use] extern crate sirena_types;
+/// # use sirena_types::AircraftCode;
+/// const PU1: AircraftCode = aircraft_code!("ПУ1");
+/// # fn main() { assert_eq!(PU1, "ПУ1"); }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate sirena_types;
+/// # use sirena_types::AircraftCode;
+/// const PU12: AircraftCode = aircraft_code!("ПУ12");
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! aircraft_code {
+    ($code: expr) => { $crate::__code_literal!(AircraftCode, 3, 3, false, $code) }
+}
+
+/// Compile-time checked `AirlineCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! airline_code {
+    ($code: expr) => { $crate::__code_literal!(AirlineCode, 2, 1, false, $code) }
+}
+
+/// Compile-time checked `AirportCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! airport_code {
+    ($code: expr) => { $crate::__code_literal!(AirportCode, 3, 0, false, $code) }
+}
+
+/// Compile-time checked `CityCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! city_code {
+    ($code: expr) => { $crate::__code_literal!(CityCode, 3, 0, false, $code) }
+}
+
+/// Compile-time checked `BookingClass` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! booking_class {
+    ($code: expr) => { $crate::__code_literal!(BookingClass, 1, 0, true, $code) }
+}
+
+/// Compile-time checked `CountryCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! country_code {
+    ($code: expr) => { $crate::__code_literal!(CountryCode, 2, 0, false, $code) }
+}
+
+/// Compile-time checked `RecordLocator` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! record_locator {
+    ($code: expr) => { $crate::__code_literal!(RecordLocator, 6, 6, false, $code) }
+}
+
+/// Compile-time checked `CurrencyCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! currency_code {
+    ($code: expr) => { $crate::__code_literal!(CurrencyCode, 3, 0, true, $code) }
+}
+
+/// Compile-time checked `SsrCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! ssr_code {
+    ($code: expr) => { $crate::__code_literal!(SsrCode, 4, 0, true, $code) }
+}
+
+/// Compile-time checked `OfficeCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! office_code {
+    ($code: expr) => { $crate::__code_literal!(OfficeCode, 6, 6, false, $code) }
+}
+
+/// Compile-time checked `MealCode` literal, like `aircraft_code!`
+#[macro_export]
+macro_rules! meal_code {
+    ($code: expr) => { $crate::__code_literal!(MealCode, 4, 0, true, $code) }
+}
+
+/// JSON schema of the string form, `pattern` matching what `FromStr` produces
+macro_rules! gen_json_schema {
+    ($t: ident, $len: expr, $pattern: expr) => {
+        gen_str_schema!($t, $len, $len, $pattern);
+    }
+}
+
+/// Validating constructor from KOI8-R bytes, shared so that it can't drift
+/// between the types. The characters are checked by the type's `FromStr`,
+/// which holds its validator:
+///
+/// ```
+/// # use sirena_types::CityCode;
+/// assert_eq!(CityCode::from_bytes(b"\xed\xef\xf7").unwrap(), "МОВ");
+/// assert!(CityCode::from_bytes(b"MOW").is_err());
+/// assert!(CityCode::from_bytes(b"\xed\xef").is_err());
+/// ```
+macro_rules! gen_from_bytes {
+    ($t: ident, $len: expr) => {
+        impl $t {
+            /// Reconstruct the code from KOI8-R bytes, validating them like `from_str` does.
+            /// Length and characters are checked before anything is built, so
+            /// a slice of the wrong length is an error rather than a panic as with
+            /// `from_bytes_unchecked`. Construction itself is always a copy of
+            /// the few bytes into the fixed-size array.
+            /// Only canonical bytes are accepted: lowercase or Ё, which `from_str`
+            /// would normalize, are errors, so `from_bytes(b).as_bytes() == b`.
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+                let code: Self = CyrillicCode::<$len>::decode(bytes)?.parse()?;
+                CyrillicCode::<$len>::check_canonical(code.as_bytes(), bytes)?;
+                Ok(code)
+            }
         }
-        let mut digits = 0;
-        for c in value.chars() {
-            if c >= 'А' && c <= 'Я' {
-                continue;
-            } else if c.is_ascii_digit() {
-                digits += 1;
-                continue;
-            } else {
-                return Err(AirlineCodeParseError::InvalidLetter(c));
+    }
+}
+
+/// `FromStr` and `is_valid`, sharing the validator
+macro_rules! gen_from_str {
+    ($t: ident, $len: expr, $is_valid: expr) => {
+        impl $t {
+            /// Check `value` like `from_str` does, without encoding it
+            pub fn is_valid(value: &str) -> bool {
+                CyrillicCode::<$len>::validate(value, $is_valid).is_ok()
             }
         }
-        // can't be 2 digits,
-        // https://ru.wikipedia.org/wiki/Код_авиакомпании_ИАТА#Внутренняя_система_кодирования_в_бывшем_СССР
-        if digits > 1 {
-            return Err(AirlineCodeParseError::TooManyDigits(digits));
+
+        impl FromStr for $t {
+            type Err = ParseError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok($t(CyrillicCode::parse(value, $is_valid)?))
+            }
         }
-        let (koi8str, _, _) = KOI8_R.encode(value);
-        let mut bytes = [0; 2];
-        bytes.copy_from_slice(&koi8str);
-        Ok(AirlineCode(bytes))
     }
 }
 
-/// 3 letter airport code
-#[derive(Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Hash, Clone, Copy)]
-pub struct AirportCode([u8; 3]);
+macro_rules! gen_from_bytes_array {
+    ($t: ident, $len: expr) => {
+        impl $t {
+            /// Wrap KOI8-R bytes without any validation, usable in `const` context.
+            /// The bytes are assumed to be a valid code already.
+            pub const fn from_bytes_array(bytes: [u8; $len]) -> Self {
+                $t(CyrillicCode::new(bytes))
+            }
 
-gen_display!(AirportCode);
+            /// KOI8-R bytes with their length known, unlike `as_bytes`
+            pub const fn as_byte_array(&self) -> &[u8; $len] {
+                &(self.0).0
+            }
+        }
 
-impl AirportCode {
-    gen_as!();
+        impl From<$t> for [u8; $len] {
+            fn from(code: $t) -> [u8; $len] {
+                (code.0).0
+            }
+        }
 
-    /// Reconstruct AirportCode from AirportCode.as_bytes()
-    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
-        let mut mine = [0; 3];
+        impl TryFrom<[u8; $len]> for $t {
+            type Error = ParseError;
 
-        mine.copy_from_slice(bytes);
-        AirportCode(mine)
+            /// Validate fixed-width KOI8-R bytes like `from_bytes` does
+            fn try_from(bytes: [u8; $len]) -> Result<Self, Self::Error> {
+                <$t>::from_bytes(&bytes)
+            }
+        }
     }
 }
 
-#[derive(Debug)]
-pub enum AirportCodeParseError {
-    InvalidLength(usize),
-    InvalidLetter(char),
+mod sealed {
+    pub trait Sealed {}
 }
 
-impl fmt::Display for AirportCodeParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            AirportCodeParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 3", len),
-            AirportCodeParseError::InvalidLetter(c) => write!(f, "invalid character {}, expected [А-Я]", c),
+/// Operations shared by all the code types, sealed so that it can't be
+/// implemented outside of this crate
+pub trait SirenaCode: sealed::Sealed {
+    /// Length of the code in KOI8-R bytes
+    const LEN: usize;
+
+    /// Name of the type, used in error messages
+    const NAME: &'static str;
+
+    fn as_str(&self) -> Cow<'_, str>;
+
+    fn as_bytes(&self) -> &[u8];
+
+    /// See the inherent `from_str_lenient` of the code types
+    fn from_str_lenient(value: &str) -> Result<Self, ParseError> where Self: Sized;
+}
+
+/// Join codes with `sep`, e.g. МОВ-ЛЕД-ОВБ for itineraries
+pub fn join_codes<'a, C: SirenaCode + 'a>(codes: impl IntoIterator<Item = &'a C>, sep: &str) -> String {
+    let mut joined = String::new();
+    for (i, code) in codes.into_iter().enumerate() {
+        if i > 0 {
+            joined.push_str(sep);
         }
+        joined.push_str(&code.as_str());
     }
+    joined
 }
 
-impl std::error::Error for AirportCodeParseError {
-    fn description(&self) -> &str {
-        "airport code parse error"
-    }
+/// Parse every input, keeping the failures in place so their
+/// position in `inputs` is known
+pub fn parse_all<T: FromStr>(inputs: &[&str]) -> Vec<Result<T, T::Err>> {
+    inputs.iter().map(|input| input.parse()).collect()
 }
 
-impl FromStr for AirportCode {
-    type Err = AirportCodeParseError;
+/// Split concatenated codes like МОВЛЕД at the middle character and parse
+/// both halves, for when `Route` is more than needed. The first half is
+/// parsed first, so its error wins. With an odd length the second half is
+/// one character longer, so fixed-length codes fail with `InvalidLength`.
+pub fn split_pair<T: FromStr>(value: &str) -> Result<(T, T), T::Err> {
+    let half = value.chars().count() / 2;
+    let split = value.char_indices().nth(half).map_or(value.len(), |(i, _)| i);
+    let (first, second) = value.split_at(split);
+    Ok((first.parse()?, second.parse()?))
+}
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.chars().count() != 3 {
-            return Err(AirportCodeParseError::InvalidLength(value.len()));
+macro_rules! gen_sirena_code {
+    ($t: ty, $len: expr) => {
+        impl sealed::Sealed for $t {}
+
+        impl SirenaCode for $t {
+            const LEN: usize = $len;
+            const NAME: &'static str = stringify!($t);
+
+            fn as_str(&self) -> Cow<'_, str> {
+                self.0.as_str()
+            }
+
+            fn as_bytes(&self) -> &[u8] {
+                self.0.as_bytes()
+            }
+
+            fn from_str_lenient(value: &str) -> Result<Self, ParseError> {
+                <$t>::from_str_lenient(value)
+            }
         }
-        for c in value.chars() {
-            if c >= 'А' && c <= 'Я' {
-                continue;
-            } else {
-                return Err(AirportCodeParseError::InvalidLetter(c));
+    }
+}
+
+/// Error returned when parsing or validating any of the codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    InvalidLength { got: usize, expected: usize },
+    /// Longer than a variable-length code allows, any length from 1 to `max` is fine
+    TooLong { got: usize, max: usize },
+    InvalidLetter { ch: char, index: usize },
+    TooManyDigits(u32),
+    IndexOutOfRange { index: usize, len: usize },
+    NoLetters,
+    BufferTooSmall { needed: usize, got: usize },
+    /// Input is empty or whitespace only
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InvalidLength { got, expected } => write!(f, "invalid length {}, expected {}", got, expected),
+            ParseError::TooLong { got, max } => write!(f, "invalid length {}, expected at most {}", got, max),
+            ParseError::InvalidLetter { ch, index } => {
+                // the allowed characters differ between the types, so they aren't named
+                // escaped so control bytes like NUL padding are visible
+                write!(f, "invalid character {} at position {}", ch.escape_debug(), index)?;
+                match latin_confusable(ch) {
+                    Some(cyrillic) => write!(f, ", looks like Latin {}, did you mean Cyrillic {}?", ch, cyrillic),
+                    None => Ok(()),
+                }
             }
+            ParseError::TooManyDigits(digits) => write!(f, "got {} digits, only 1 allowed", digits),
+            ParseError::IndexOutOfRange { index, len } => write!(f, "index {} out of range for length {}", index, len),
+            ParseError::NoLetters => write!(f, "no letters, expected at least one of [А-Я]"),
+            ParseError::BufferTooSmall { needed, got } => write!(f, "buffer of {} bytes too small, need {}", got, needed),
+            ParseError::Empty => write!(f, "empty input"),
         }
-        let (koi8str, _, _) = KOI8_R.encode(value);
-        let mut bytes = [0; 3];
-        bytes.copy_from_slice(&koi8str);
-        Ok(AirportCode(bytes))
     }
 }
 
-/// 3 letter airport code
-#[derive(Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Hash, Clone, Copy)]
-pub struct CityCode([u8; 3]);
+impl ParseError {
+    /// Length the input should have had, the `LEN` of the type being parsed.
+    /// `None` for `TooLong`, where there's no single right length
+    pub fn expected_len(&self) -> Option<usize> {
+        match *self {
+            ParseError::InvalidLength { expected, .. } => Some(expected),
+            _ => None,
+        }
+    }
 
-gen_display!(CityCode);
+    /// Longest length allowed, for the variable-length codes
+    pub fn max_len(&self) -> Option<usize> {
+        match *self {
+            ParseError::TooLong { max, .. } => Some(max),
+            _ => None,
+        }
+    }
+}
 
-impl CityCode {
-    gen_as!();
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
 
-    /// Reconstruct CityCode from CityCode.as_bytes()
-    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
-        let mut mine = [0; 3];
+/// `ParseError` along with the input that failed and the type it was parsed as.
+/// Not `Copy` as it owns the input, see `from_str_verbose`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrorOwned {
+    input: String,
+    type_name: &'static str,
+    error: ParseError,
+}
 
-        mine.copy_from_slice(bytes);
-        CityCode(mine)
+impl ParseErrorOwned {
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn error(&self) -> ParseError {
+        self.error
+    }
+}
+
+impl fmt::Display for ParseErrorOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to parse {:?} as {}: {}", self.input, self.type_name, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseErrorOwned {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
     }
 }
 
-#[derive(Debug)]
-pub enum CityCodeParseError {
-    InvalidLength(usize),
-    InvalidLetter(char),
+/// Stored bytes don't make a valid code, which is only possible through
+/// `from_bytes_unchecked` or `from_bytes_array`. See `try_as_str`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    error: ParseError,
+}
+
+impl DecodeError {
+    /// What `from_bytes` reports for the stored bytes
+    pub fn error(&self) -> ParseError {
+        self.error
+    }
 }
 
-impl fmt::Display for CityCodeParseError {
+impl fmt::Display for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            CityCodeParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 3", len),
-            CityCodeParseError::InvalidLetter(c) => write!(f, "invalid character {}, expected [А-Я]", c),
-        }
+        write!(f, "corrupted code: {}", self.error)
     }
 }
 
-impl std::error::Error for CityCodeParseError {
-    fn description(&self) -> &str {
-        "city code parse error"
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
     }
 }
 
-impl FromStr for CityCode {
-    type Err = CityCodeParseError;
+/// KOI8-R maps every byte to exactly one char, so this never needs to allocate
+fn decode_byte(b: u8) -> char {
+    if b.is_ascii() {
+        return b as char;
+    }
+    let mut decoder = KOI8_R.new_decoder_without_bom_handling();
+    let mut buf = [0; 4];
+    let (_, _, written) = decoder.decode_to_utf8_without_replacement(&[b], &mut buf, true);
+    str::from_utf8(&buf[..written]).ok()
+        .and_then(|s| s.chars().next())
+        .unwrap_or(core::char::REPLACEMENT_CHARACTER)
+}
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.chars().count() != 3 {
-            return Err(CityCodeParseError::InvalidLength(value.len()));
-        }
-        for c in value.chars() {
-            if c >= 'А' && c <= 'Я' {
-                continue;
-            } else {
-                return Err(CityCodeParseError::InvalidLetter(c));
-            }
+/// ASCII transliteration of an uppercase Cyrillic letter as in ICAO Doc 9303,
+/// the one used in passports. ASCII is kept as is.
+fn transliterate(c: char, out: &mut String) {
+    let latin = match c {
+        'А' => "A", 'Б' => "B", 'В' => "V", 'Г' => "G", 'Д' => "D", 'Е' => "E",
+        'Ж' => "ZH", 'З' => "Z", 'И' => "I", 'Й' => "I", 'К' => "K", 'Л' => "L",
+        'М' => "M", 'Н' => "N", 'О' => "O", 'П' => "P", 'Р' => "R", 'С' => "S",
+        'Т' => "T", 'У' => "U", 'Ф' => "F", 'Х' => "KH", 'Ц' => "TS", 'Ч' => "CH",
+        'Ш' => "SH", 'Щ' => "SHCH", 'Ъ' => "IE", 'Ы' => "Y", 'Ь' => "", 'Э' => "E",
+        'Ю' => "IU", 'Я' => "IA",
+        c if c.is_ascii() => return out.push(c),
+        _ => "?",
+    };
+    out.push_str(latin)
+}
+
+/// А to Я, the letters `is_letter` accepts, for generating valid codes
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+const LETTERS: &[char] = &[
+    'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
+    'Р', 'С', 'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
+];
+
+/// `LETTERS` and the digits, what `is_letter_or_digit` accepts
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+const LETTERS_AND_DIGITS: &[char] = &[
+    'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
+    'Р', 'С', 'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+/// KOI8-R codes of А to Я
+const KOI8_LETTERS: [u8; 32] = [
+    0xe1, 0xe2, 0xf7, 0xe7, 0xe4, 0xe5, 0xf6, 0xfa, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef, 0xf0,
+    0xf2, 0xf3, 0xf4, 0xf5, 0xe6, 0xe8, 0xe3, 0xfe, 0xfb, 0xfd, 0xff, 0xf9, 0xf8, 0xfc, 0xe0, 0xf1,
+];
+
+/// Encode a code literal to KOI8-R in `const` context, panicking (so failing
+/// the build) unless it is N characters of uppercase А-Я, with at most
+/// `max_digits` ASCII digits and Latin A-Z only if `latin` is set.
+/// Used by `aircraft_code!` and the other literal macros.
+#[doc(hidden)]
+pub const fn encode_literal<const N: usize>(code: &str, max_digits: usize, latin: bool) -> [u8; N] {
+    let utf8 = code.as_bytes();
+    let mut bytes = [0; N];
+    let (mut i, mut len, mut digits) = (0, 0, 0);
+    while i < utf8.len() {
+        assert!(len < N, "code literal is too long");
+        let b = utf8[i];
+        if b.is_ascii_digit() {
+            digits += 1;
+            assert!(digits <= max_digits, "too many digits in code literal");
+            bytes[len] = b;
+            i += 1;
+        } else if b.is_ascii_uppercase() {
+            assert!(latin, "Latin letter in code literal, expected [А-Я]");
+            bytes[len] = b;
+            i += 1;
+        } else {
+            // А to Я are D0 90 to D0 AF in UTF-8
+            assert!(i + 1 < utf8.len() && b == 0xd0 && utf8[i + 1] >= 0x90 && utf8[i + 1] <= 0xaf,
+                    "invalid character in code literal");
+            bytes[len] = KOI8_LETTERS[(utf8[i + 1] - 0x90) as usize];
+            i += 2;
         }
-        let (koi8str, _, _) = KOI8_R.encode(value);
-        let mut bytes = [0; 3];
-        bytes.copy_from_slice(&koi8str);
-        Ok(CityCode(bytes))
+        len += 1;
     }
+    assert!(len == N, "code literal is too short");
+    bytes
 }
 
-#[test]
-fn test_encode_aircraft() {
+/// Cyrillic letter an uppercase Latin one looks exactly like
+fn latin_confusable(c: char) -> Option<char> {
+    Some(match c {
+        'A' => 'А', 'B' => 'В', 'E' => 'Е', 'K' => 'К', 'M' => 'М', 'H' => 'Н',
+        'O' => 'О', 'P' => 'Р', 'C' => 'С', 'T' => 'Т', 'X' => 'Х',
+        _ => return None,
+    })
+}
+
+/// Cyrillic letter on the same key of the Russian ЙЦУКЕН layout as a Latin
+/// QWERTY one, ignoring case. Covers the punctuation keys holding Х Ъ Ж Э Б Ю Ё
+fn keyboard_cyrillic(c: char) -> Option<char> {
+    Some(match c.to_ascii_uppercase() {
+        'Q' => 'Й', 'W' => 'Ц', 'E' => 'У', 'R' => 'К', 'T' => 'Е', 'Y' => 'Н', 'U' => 'Г',
+        'I' => 'Ш', 'O' => 'Щ', 'P' => 'З', '[' | '{' => 'Х', ']' | '}' => 'Ъ',
+        'A' => 'Ф', 'S' => 'Ы', 'D' => 'В', 'F' => 'А', 'G' => 'П', 'H' => 'Р', 'J' => 'О',
+        'K' => 'Л', 'L' => 'Д', ';' | ':' => 'Ж', '\'' | '"' => 'Э',
+        'Z' => 'Я', 'X' => 'Ч', 'C' => 'С', 'V' => 'М', 'B' => 'И', 'N' => 'Т', 'M' => 'Ь',
+        ',' | '<' => 'Б', '.' | '>' => 'Ю', '`' | '~' => 'Ё',
+        _ => return None,
+    })
+}
+
+/// Codes are always stored uppercase, lowercase Cyrillic is accepted on input.
+/// Ё is folded to Е as Sirena does.
+fn normalize(c: char) -> char {
+    match c {
+        'Ё' | 'ё' => 'Е',
+        'а'..='я' => c.to_uppercase().next().unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn is_letter(c: char) -> bool {
+    ('А'..='Я').contains(&c)
+}
+
+fn is_letter_or_digit(c: char) -> bool {
+    c.is_ascii_digit() || is_letter(c)
+}
+
+fn is_letter_or_latin(c: char) -> bool {
+    c.is_ascii_uppercase() || is_letter(c)
+}
+
+/// Whether `from_str` accepts `c` as a letter of a Cyrillic code: А-Я,
+/// lowercase а-я and Ё/ё, the latter two normalized on parse.
+/// For filtering input before parsing, e.g. in UI fields
+pub fn is_code_letter(c: char) -> bool {
+    is_letter(normalize(c))
+}
+
+/// `is_code_letter` or an ASCII digit, as allowed in airline and aircraft codes
+pub fn is_code_char(c: char) -> bool {
+    is_letter_or_digit(normalize(c))
+}
+
+/// KOI8-R bytes of a fixed-length code, shared storage of all public code types.
+/// With `utf8-storage` the decoded form is kept alongside, see `Utf8Buf`
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+struct CyrillicCode<const N: usize>([u8; N], #[cfg(feature = "utf8-storage")] Utf8Buf);
+
+/// Longest code, in UTF-8 bytes: 6 KOI8-R bytes of at most 3 bytes each
+#[cfg(feature = "utf8-storage")]
+const MAX_UTF8_LEN: usize = 18;
+
+/// UTF-8 of a code, decoded once when it is built so `as_str` can borrow it
+#[cfg(feature = "utf8-storage")]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+struct Utf8Buf {
+    len: u8,
+    bytes: [u8; MAX_UTF8_LEN],
+}
+
+/// Chars of KOI8-R bytes 0x80 to 0xFF, for decoding in `const` context
+#[cfg(feature = "utf8-storage")]
+const KOI8_UPPER: [char; 128] = [
+    '\u{2500}', '\u{2502}', '\u{250c}', '\u{2510}', '\u{2514}', '\u{2518}', '\u{251c}', '\u{2524}',
+    '\u{252c}', '\u{2534}', '\u{253c}', '\u{2580}', '\u{2584}', '\u{2588}', '\u{258c}', '\u{2590}',
+    '\u{2591}', '\u{2592}', '\u{2593}', '\u{2320}', '\u{25a0}', '\u{2219}', '\u{221a}', '\u{2248}',
+    '\u{2264}', '\u{2265}', '\u{00a0}', '\u{2321}', '\u{00b0}', '\u{00b2}', '\u{00b7}', '\u{00f7}',
+    '\u{2550}', '\u{2551}', '\u{2552}', '\u{0451}', '\u{2553}', '\u{2554}', '\u{2555}', '\u{2556}',
+    '\u{2557}', '\u{2558}', '\u{2559}', '\u{255a}', '\u{255b}', '\u{255c}', '\u{255d}', '\u{255e}',
+    '\u{255f}', '\u{2560}', '\u{2561}', '\u{0401}', '\u{2562}', '\u{2563}', '\u{2564}', '\u{2565}',
+    '\u{2566}', '\u{2567}', '\u{2568}', '\u{2569}', '\u{256a}', '\u{256b}', '\u{256c}', '\u{00a9}',
+    '\u{044e}', '\u{0430}', '\u{0431}', '\u{0446}', '\u{0434}', '\u{0435}', '\u{0444}', '\u{0433}',
+    '\u{0445}', '\u{0438}', '\u{0439}', '\u{043a}', '\u{043b}', '\u{043c}', '\u{043d}', '\u{043e}',
+    '\u{043f}', '\u{044f}', '\u{0440}', '\u{0441}', '\u{0442}', '\u{0443}', '\u{0436}', '\u{0432}',
+    '\u{044c}', '\u{044b}', '\u{0437}', '\u{0448}', '\u{044d}', '\u{0449}', '\u{0447}', '\u{044a}',
+    '\u{042e}', '\u{0410}', '\u{0411}', '\u{0426}', '\u{0414}', '\u{0415}', '\u{0424}', '\u{0413}',
+    '\u{0425}', '\u{0418}', '\u{0419}', '\u{041a}', '\u{041b}', '\u{041c}', '\u{041d}', '\u{041e}',
+    '\u{041f}', '\u{042f}', '\u{0420}', '\u{0421}', '\u{0422}', '\u{0423}', '\u{0416}', '\u{0412}',
+    '\u{042c}', '\u{042b}', '\u{0417}', '\u{0428}', '\u{042d}', '\u{0429}', '\u{0427}', '\u{042a}',
+];
+
+impl<const N: usize> CyrillicCode<N> {
+    #[cfg(not(feature = "utf8-storage"))]
+    const fn new(bytes: [u8; N]) -> Self {
+        CyrillicCode(bytes)
+    }
+
+    #[cfg(feature = "utf8-storage")]
+    const fn new(bytes: [u8; N]) -> Self {
+        assert!(N * 3 <= MAX_UTF8_LEN, "code too long for utf8-storage");
+        let mut utf8 = Utf8Buf { len: 0, bytes: [0; MAX_UTF8_LEN] };
+        let mut i = 0;
+        while i < N {
+            let c = if bytes[i] < 0x80 { bytes[i] as char } else { KOI8_UPPER[(bytes[i] - 0x80) as usize] };
+            let len = utf8.len as usize;
+            let encoded = c.encode_utf8(utf8.bytes.split_at_mut(len).1).len();
+            utf8.len = (len + encoded) as u8;
+            i += 1;
+        }
+        CyrillicCode(bytes, utf8)
+    }
+
+    #[cfg(feature = "utf8-storage")]
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.utf8())
+    }
+
+    #[cfg(feature = "utf8-storage")]
+    fn utf8(&self) -> &str {
+        str::from_utf8(&self.1.bytes[..self.1.len as usize]).expect("new writes valid UTF-8")
+    }
+
+    #[cfg(not(feature = "utf8-storage"))]
+    fn as_str(&self) -> Cow<'_, str> {
+        // ASCII is the same in KOI8-R and UTF-8, borrow it as is
+        if let Ok(ascii) = str::from_utf8(&self.0) {
+            if ascii.is_ascii() {
+                return Cow::Borrowed(ascii);
+            }
+        }
+        // no BOM sniffing: ЧЪ is FE FF in KOI8-R, which looks like a UTF-16 BOM
+        let (s, _) = KOI8_R.decode_without_bom_handling(&self.0);
+        s
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[cfg(feature = "utf8-storage")]
+    fn with_str<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+        f(self.utf8())
+    }
+
+    /// Decode into a stack buffer, every KOI8-R byte takes at most 4 bytes of UTF-8
+    #[cfg(not(feature = "utf8-storage"))]
+    fn with_str<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+        let mut buf = [[0u8; 4]; N];
+        let buf = buf.as_flattened_mut();
+        let mut len = 0;
+        for c in self.chars() {
+            len += c.encode_utf8(&mut buf[len..]).len();
+        }
+        f(str::from_utf8(&buf[..len]).expect("encode_utf8 writes valid UTF-8"))
+    }
+
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.iter().cloned().map(decode_byte)
+    }
+
+    fn eq_str(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+
+    /// Check length and `is_valid` on every normalized character, without allocating
+    fn validate(value: &str, is_valid: fn(char) -> bool) -> Result<(), ParseError> {
+        if value.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let len = value.chars().count();
+        if len != N {
+            return Err(ParseError::InvalidLength { got: len, expected: N });
+        }
+        if let Some((index, ch)) = value.chars().map(normalize).enumerate().find(|&(_, c)| !is_valid(c)) {
+            return Err(ParseError::InvalidLetter { ch, index });
+        }
+        Ok(())
+    }
+
+    /// Validate, normalize, then encode to KOI8-R
+    fn parse(value: &str, is_valid: fn(char) -> bool) -> Result<Self, ParseError> {
+        Self::validate(value, is_valid)?;
+        let value: String = value.chars().map(normalize).collect();
+        Self::encode(&value).map(CyrillicCode::new)
+    }
+
+    /// Encode into exactly N KOI8-R bytes, reporting the character the encoder
+    /// had to substitute rather than letting `copy_from_slice` panic
+    fn encode(value: &str) -> Result<[u8; N], ParseError> {
+        let (koi8str, _, had_errors) = KOI8_R.encode(value);
+        if had_errors || koi8str.len() != N {
+            let mut buf = [0; 4];
+            let unencodable = value.chars().enumerate().find(|(_, c)| {
+                let (encoded, _, had_errors) = KOI8_R.encode(c.encode_utf8(&mut buf));
+                had_errors || encoded.len() != 1
+            });
+            return Err(match unencodable {
+                Some((index, ch)) => ParseError::InvalidLetter { ch, index },
+                None => ParseError::InvalidLength { got: koi8str.len(), expected: N },
+            });
+        }
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&koi8str);
+        Ok(bytes)
+    }
+
+    /// Error at the first byte `parse` normalized, as `bytes` weren't stored as is
+    fn check_canonical(parsed: &[u8], bytes: &[u8]) -> Result<(), ParseError> {
+        match parsed.iter().zip(bytes).position(|(p, b)| p != b) {
+            Some(index) => Err(ParseError::InvalidLetter { ch: decode_byte(bytes[index]), index }),
+            None => Ok(()),
+        }
+    }
+
+    /// Decode KOI8-R bytes so the public type can validate them with its `FromStr`.
+    /// Control bytes, such as NUL padding of fixed-width records, are rejected
+    /// here rather than left to the validators
+    fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, ParseError> {
+        if bytes.len() != N {
+            return Err(ParseError::InvalidLength { got: bytes.len(), expected: N });
+        }
+        if let Some((index, &b)) = bytes.iter().enumerate().find(|&(_, b)| b.is_ascii_control()) {
+            return Err(ParseError::InvalidLetter { ch: char::from(b), index });
+        }
+        let (s, _) = KOI8_R.decode_without_bom_handling(bytes);
+        Ok(s)
+    }
+}
+
+impl<const N: usize> Default for CyrillicCode<N> {
+    fn default() -> Self {
+        CyrillicCode::new([b' '; N])
+    }
+}
+
+impl<const N: usize> fmt::Display for CyrillicCode<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.with_str(|s| f.pad(s))
+    }
+}
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct AircraftCode(CyrillicCode<3>);
+
+gen_display!(AircraftCode);
+gen_as_ref!(AircraftCode);
+gen_borrow!(AircraftCode);
+gen_into_string!(AircraftCode);
+gen_partial_eq!(AircraftCode);
+gen_try_from!(AircraftCode);
+gen_serialize!(AircraftCode);
+gen_deserialize!(AircraftCode, 3);
+gen_sirena_code!(AircraftCode, 3);
+gen_from_bytes_array!(AircraftCode, 3);
+gen_from_bytes!(AircraftCode, 3);
+gen_len!(AircraftCode, 3);
+gen_default!(AircraftCode);
+gen_json_schema!(AircraftCode, 3, "^[А-Я0-9]{3}$");
+impl AircraftCode {
+    gen_as!();
+
+    /// Like `from_str`, but also rejects all-digit codes like 123.
+    /// Opt-in as Sirena does use numeric codes for some aircraft
+    pub fn from_str_strict(value: &str) -> Result<Self, ParseError> {
+        let code: Self = value.parse()?;
+        if !code.chars().any(is_letter) {
+            return Err(ParseError::NoLetters);
+        }
+        Ok(code)
+    }
+
+    /// Code is made of letters only, like ТУШ
+    pub fn is_letters_only(&self) -> bool {
+        self.chars().all(is_letter)
+    }
+
+    /// Number made of the digits after the last letter, like 1 in ПУ1.
+    /// `None` if the code ends with a letter or has no letters at all
+    pub fn trailing_number(&self) -> Option<u8> {
+        let mut number = None;
+        let mut seen_letter = false;
+        for c in self.chars() {
+            match c.to_digit(10) {
+                // at most two digits can follow a letter, so this fits
+                Some(d) if seen_letter => number = Some(number.unwrap_or(0) * 10 + d as u8),
+                Some(_) => {}
+                None => {
+                    seen_letter = true;
+                    number = None;
+                }
+            }
+        }
+        number
+    }
+}
+
+#[deprecated(note = "use ParseError")]
+pub type AircraftCodeParseError = ParseError;
+
+gen_from_str!(AircraftCode, 3, is_letter_or_digit);
+
+/// 2 character airline code. Codes of the internal USSR numbering have
+/// one digit, which may be in either position:
+///
+/// ```
+/// # use sirena_types::AirlineCode;
+/// assert!("С7".parse::<AirlineCode>().is_ok());
+/// assert!("5Н".parse::<AirlineCode>().is_ok());
+/// assert!("77".parse::<AirlineCode>().is_err());
+/// ```
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct AirlineCode(CyrillicCode<2>);
+
+gen_display!(AirlineCode);
+gen_as_ref!(AirlineCode);
+gen_borrow!(AirlineCode);
+gen_into_string!(AirlineCode);
+gen_partial_eq!(AirlineCode);
+gen_try_from!(AirlineCode);
+gen_serialize!(AirlineCode);
+gen_deserialize!(AirlineCode, 2);
+gen_sirena_code!(AirlineCode, 2);
+gen_from_bytes_array!(AirlineCode, 2);
+gen_from_bytes!(AirlineCode, 2);
+gen_len!(AirlineCode, 2);
+gen_default!(AirlineCode);
+gen_json_schema!(AirlineCode, 2, "^([А-Я][А-Я0-9]|[0-9][А-Я])$");
+
+impl AirlineCode {
+    gen_as!();
+
+    /// Reconstruct AirlineCode from AirlineCode.as_bytes()
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be exactly 2 bytes long and hold a valid KOI8-R code,
+    /// otherwise this panics or produces a code that fails validation.
+    /// Use `from_bytes` for untrusted input.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
+        let mut mine = [0; 2];
+
+        mine.copy_from_slice(bytes);
+        AirlineCode(CyrillicCode::new(mine))
+    }
+
+    /// Check `value` like `from_str` does, without encoding it
+    pub fn is_valid(value: &str) -> bool {
+        AirlineCode::validate(value).is_ok()
+    }
+
+    fn validate(value: &str) -> Result<(), ParseError> {
+        CyrillicCode::<2>::validate(value, is_letter_or_digit)?;
+        let digits = value.chars().filter(char::is_ascii_digit).count() as u32;
+        // can't be 2 digits, the position of a single one isn't constrained,
+        // https://ru.wikipedia.org/wiki/Код_авиакомпании_ИАТА#Внутренняя_система_кодирования_в_бывшем_СССР
+        if digits > 1 {
+            return Err(ParseError::TooManyDigits(digits));
+        }
+        Ok(())
+    }
+
+    /// Code uses the internal USSR numbering, one letter and one digit like С7
+    pub fn has_digit(&self) -> bool {
+        self.chars().any(|c| c.is_ascii_digit())
+    }
+
+    /// Value of the digit for codes using the internal USSR numbering
+    pub fn digit(&self) -> Option<u8> {
+        self.chars().filter_map(|c| c.to_digit(10)).next().map(|d| d as u8)
+    }
+
+    /// Code is made of two letters like СУ
+    pub fn is_alpha(&self) -> bool {
+        !self.has_digit()
+    }
+}
+
+#[deprecated(note = "use ParseError")]
+pub type AirlineCodeParseError = ParseError;
+
+impl FromStr for AirlineCode {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AirlineCode::validate(value)?;
+        Ok(AirlineCode(CyrillicCode::parse(value, is_letter_or_digit)?))
+    }
+}
+
+/// 3 letter airport code
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct AirportCode(CyrillicCode<3>);
+
+gen_display!(AirportCode, latin);
+gen_as_ref!(AirportCode);
+gen_borrow!(AirportCode);
+gen_into_string!(AirportCode);
+gen_partial_eq!(AirportCode);
+gen_try_from!(AirportCode);
+gen_serialize!(AirportCode);
+gen_deserialize!(AirportCode, 3);
+gen_sirena_code!(AirportCode, 3);
+gen_from_bytes_array!(AirportCode, 3);
+gen_from_bytes!(AirportCode, 3);
+gen_len!(AirportCode, 3);
+gen_default!(AirportCode);
+gen_json_schema!(AirportCode, 3, "^[А-Я]{3}$");
+
+impl AirportCode {
+    gen_as!();
+
+    /// Reconstruct AirportCode from AirportCode.as_bytes()
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be exactly 3 bytes long and hold a valid KOI8-R code,
+    /// otherwise this panics or produces a code that fails validation.
+    /// Use `from_bytes` for untrusted input.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
+        let mut mine = [0; 3];
+
+        mine.copy_from_slice(bytes);
+        AirportCode(CyrillicCode::new(mine))
+    }
+
+    /// Latin IATA equivalent of the code, if there is a well-known one
+    pub fn to_latin(&self) -> Option<&'static str> {
+        tables::lookup(tables::LATIN_AIRPORTS, &self.as_str())
+    }
+
+    /// Find the code by its Latin IATA equivalent, ignoring case
+    pub fn from_latin(code: &str) -> Option<Self> {
+        tables::reverse_lookup(tables::LATIN_AIRPORTS, code).and_then(|c| c.parse().ok())
+    }
+
+    /// Human-readable name of the airport, if known. Only the major Russian
+    /// and CIS airports are covered
+    #[cfg(feature = "airport-names")]
+    pub fn name(&self) -> Option<&'static str> {
+        tables::lookup(tables::AIRPORT_NAMES, &self.as_str())
+    }
+
+    /// Airport uses the same code as `city`, like СОЧ
+    pub fn same_code_as(&self, city: &CityCode) -> bool {
+        self.as_bytes() == city.as_bytes()
+    }
+
+    /// The only airport of `city`. `None` if the city is unknown
+    /// or has several airports, like МОВ
+    pub fn single_airport_of(city: CityCode) -> Option<Self> {
+        match Self::airports_of(city) {
+            &[airport] => Some(airport),
+            _ => None,
+        }
+    }
+
+    /// All airports of `city`, empty if the city is unknown
+    pub fn airports_of(city: CityCode) -> &'static [AirportCode] {
+        let city = city.as_str();
+        tables::CITY_AIRPORTS.binary_search_by(|&(key, _)| key.cmp(&city))
+            .map_or(&[], |i| tables::CITY_AIRPORTS[i].1)
+    }
+
+    /// City the airport belongs to, if known
+    pub fn city(&self) -> Option<CityCode> {
+        tables::lookup(tables::AIRPORT_CITIES, &self.as_str()).and_then(|c| c.parse().ok())
+    }
+}
+
+#[deprecated(note = "use ParseError")]
+pub type AirportCodeParseError = ParseError;
+
+gen_from_str!(AirportCode, 3, is_letter);
+
+/// 3 letter airport code
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct CityCode(CyrillicCode<3>);
+
+gen_display!(CityCode, latin);
+gen_as_ref!(CityCode);
+gen_borrow!(CityCode);
+gen_into_string!(CityCode);
+gen_partial_eq!(CityCode);
+gen_try_from!(CityCode);
+gen_serialize!(CityCode);
+gen_deserialize!(CityCode, 3);
+gen_sirena_code!(CityCode, 3);
+gen_from_bytes_array!(CityCode, 3);
+gen_from_bytes!(CityCode, 3);
+gen_len!(CityCode, 3);
+gen_default!(CityCode);
+gen_json_schema!(CityCode, 3, "^[А-Я]{3}$");
+
+impl CityCode {
+    gen_as!();
+
+    /// Reconstruct CityCode from CityCode.as_bytes()
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be exactly 3 bytes long and hold a valid KOI8-R code,
+    /// otherwise this panics or produces a code that fails validation.
+    /// Use `from_bytes` for untrusted input.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
+        let mut mine = [0; 3];
+
+        mine.copy_from_slice(bytes);
+        CityCode(CyrillicCode::new(mine))
+    }
+
+    /// Latin IATA equivalent of the code, if there is a well-known one
+    pub fn to_latin(&self) -> Option<&'static str> {
+        tables::lookup(tables::LATIN_CITIES, &self.as_str())
+    }
+
+    /// Find the code by its Latin IATA equivalent, ignoring case
+    pub fn from_latin(code: &str) -> Option<Self> {
+        tables::reverse_lookup(tables::LATIN_CITIES, code).and_then(|c| c.parse().ok())
+    }
+}
+
+#[deprecated(note = "use ParseError")]
+pub type CityCodeParseError = ParseError;
+
+gen_from_str!(CityCode, 3, is_letter);
+
+/// Booking class (subclass) letter, Cyrillic or Latin
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct BookingClass(CyrillicCode<1>);
+
+gen_display!(BookingClass);
+gen_as_ref!(BookingClass);
+gen_borrow!(BookingClass);
+gen_into_string!(BookingClass);
+gen_partial_eq!(BookingClass);
+gen_try_from!(BookingClass);
+gen_serialize!(BookingClass);
+gen_deserialize!(BookingClass, 1);
+gen_sirena_code!(BookingClass, 1);
+gen_from_bytes_array!(BookingClass, 1);
+gen_from_bytes!(BookingClass, 1);
+gen_len!(BookingClass, 1);
+gen_default!(BookingClass);
+gen_json_schema!(BookingClass, 1, "^[А-ЯA-Z]$");
+
+impl BookingClass {
+    gen_as!();
+}
+
+gen_from_str!(BookingClass, 1, is_letter_or_latin);
+
+/// 2 letter country code, e.g. РФ
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct CountryCode(CyrillicCode<2>);
+
+gen_display!(CountryCode);
+gen_as_ref!(CountryCode);
+gen_borrow!(CountryCode);
+gen_into_string!(CountryCode);
+gen_partial_eq!(CountryCode);
+gen_try_from!(CountryCode);
+gen_serialize!(CountryCode);
+gen_deserialize!(CountryCode, 2);
+gen_sirena_code!(CountryCode, 2);
+gen_from_bytes_array!(CountryCode, 2);
+gen_from_bytes!(CountryCode, 2);
+gen_len!(CountryCode, 2);
+gen_default!(CountryCode);
+gen_json_schema!(CountryCode, 2, "^[А-Я]{2}$");
+
+impl CountryCode {
+    gen_as!();
+
+    /// Reconstruct CountryCode from CountryCode.as_bytes()
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be exactly 2 bytes long and hold a valid KOI8-R code,
+    /// otherwise this panics or produces a code that fails validation.
+    /// Use `from_bytes` for untrusted input.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Self {
+        let mut mine = [0; 2];
+
+        mine.copy_from_slice(bytes);
+        CountryCode(CyrillicCode::new(mine))
+    }
+}
+
+gen_from_str!(CountryCode, 2, is_letter);
+
+/// 6 character booking record locator (PNR), Cyrillic letters and digits
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct RecordLocator(CyrillicCode<6>);
+
+gen_display!(RecordLocator);
+gen_as_ref!(RecordLocator);
+gen_borrow!(RecordLocator);
+gen_into_string!(RecordLocator);
+gen_partial_eq!(RecordLocator);
+gen_try_from!(RecordLocator);
+gen_serialize!(RecordLocator);
+gen_deserialize!(RecordLocator, 6);
+gen_sirena_code!(RecordLocator, 6);
+gen_from_bytes_array!(RecordLocator, 6);
+gen_from_bytes!(RecordLocator, 6);
+gen_len!(RecordLocator, 6);
+gen_default!(RecordLocator);
+gen_json_schema!(RecordLocator, 6, "^[А-Я0-9]{6}$");
+
+impl RecordLocator {
+    gen_as!();
+}
+
+gen_from_str!(RecordLocator, 6, is_letter_or_digit);
+
+/// 3 letter currency code, Cyrillic (РУБ) or Latin (ISO 4217)
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct CurrencyCode(CyrillicCode<3>);
+
+gen_display!(CurrencyCode);
+gen_as_ref!(CurrencyCode);
+gen_borrow!(CurrencyCode);
+gen_into_string!(CurrencyCode);
+gen_partial_eq!(CurrencyCode);
+gen_try_from!(CurrencyCode);
+gen_serialize!(CurrencyCode);
+gen_deserialize!(CurrencyCode, 3);
+gen_sirena_code!(CurrencyCode, 3);
+gen_from_bytes_array!(CurrencyCode, 3);
+gen_from_bytes!(CurrencyCode, 3);
+gen_len!(CurrencyCode, 3);
+gen_default!(CurrencyCode);
+gen_json_schema!(CurrencyCode, 3, "^[А-ЯA-Z]{3}$");
+
+impl CurrencyCode {
+    gen_as!();
+}
+
+gen_from_str!(CurrencyCode, 3, is_letter_or_latin);
+
+/// 4 letter special service request code, like WCHR or VGML
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct SsrCode(CyrillicCode<4>);
+
+gen_display!(SsrCode);
+gen_as_ref!(SsrCode);
+gen_borrow!(SsrCode);
+gen_into_string!(SsrCode);
+gen_partial_eq!(SsrCode);
+gen_try_from!(SsrCode);
+gen_serialize!(SsrCode);
+gen_deserialize!(SsrCode, 4);
+gen_sirena_code!(SsrCode, 4);
+gen_from_bytes_array!(SsrCode, 4);
+gen_from_bytes!(SsrCode, 4);
+gen_len!(SsrCode, 4);
+gen_default!(SsrCode);
+gen_json_schema!(SsrCode, 4, "^[А-ЯA-Z]{4}$");
+
+impl SsrCode {
+    gen_as!();
+}
+
+gen_from_str!(SsrCode, 4, is_letter_or_latin);
+
+/// 6 character office (пульт) code of an agency terminal, like МОВ001.
+/// Made of Cyrillic letters and digits, usually a city code followed by a number
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct OfficeCode(CyrillicCode<6>);
+
+gen_display!(OfficeCode);
+gen_as_ref!(OfficeCode);
+gen_borrow!(OfficeCode);
+gen_into_string!(OfficeCode);
+gen_partial_eq!(OfficeCode);
+gen_try_from!(OfficeCode);
+gen_serialize!(OfficeCode);
+gen_deserialize!(OfficeCode, 6);
+gen_sirena_code!(OfficeCode, 6);
+gen_from_bytes_array!(OfficeCode, 6);
+gen_from_bytes!(OfficeCode, 6);
+gen_len!(OfficeCode, 6);
+gen_default!(OfficeCode);
+gen_json_schema!(OfficeCode, 6, "^[А-Я0-9]{6}$");
+
+impl OfficeCode {
+    gen_as!();
+}
+
+gen_from_str!(OfficeCode, 6, is_letter_or_digit);
+
+/// 4 letter SSR-shaped code meant for meals, like VGML or KSML. It's
+/// validated just like `SsrCode`, so WCHR parses too; `is_standard`
+/// tells the IATA meal codes apart
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct MealCode(CyrillicCode<4>);
+
+gen_display!(MealCode);
+gen_as_ref!(MealCode);
+gen_borrow!(MealCode);
+gen_into_string!(MealCode);
+gen_partial_eq!(MealCode);
+gen_try_from!(MealCode);
+gen_serialize!(MealCode);
+gen_deserialize!(MealCode, 4);
+gen_sirena_code!(MealCode, 4);
+gen_from_bytes_array!(MealCode, 4);
+gen_from_bytes!(MealCode, 4);
+gen_len!(MealCode, 4);
+gen_default!(MealCode);
+gen_json_schema!(MealCode, 4, "^[А-ЯA-Z]{4}$");
+
+impl MealCode {
+    gen_as!();
+
+    /// One of the IATA meal codes in `MEALS`
+    pub fn is_standard(&self) -> bool {
+        MEALS.binary_search_by(|meal| meal.as_bytes().cmp(self.as_bytes())).is_ok()
+    }
+}
+
+/// IATA meal SSR codes, sorted
+pub static MEALS: &[&str] = &[
+    "AVML", "BBML", "BLML", "CHML", "DBML", "FPML", "GFML", "HFML", "HNML", "JPML", "KSML", "LCML",
+    "LFML", "LPML", "LSML", "MOML", "NLML", "RVML", "SFML", "SPML", "VGML", "VJML", "VLML", "VOML",
+];
+
+gen_from_str!(MealCode, 4, is_letter_or_latin);
+
+#[test]
+fn test_encode_aircraft() {
     let a = "ПУ1";
     let code = AircraftCode::from_str(a).unwrap();
     println!("{:?}", code);
     assert_eq!(a, &format!("{}", code));
     assert_eq!(a, &code.as_str());
 }
+
+#[test]
+fn test_from_bytes() {
+    let code = AirportCode::from_str("МОВ").unwrap();
+    assert_eq!(AirportCode::from_bytes(code.as_bytes()).unwrap(), code);
+    assert!(AirportCode::from_bytes(b"MOW").is_err());
+    assert!(AirlineCode::from_bytes(b"12").is_err());
+    assert!(CityCode::from_bytes(&code.as_bytes()[..2]).is_err());
+    // lowercase мов and Ё aren't stored forms, so they aren't rewritten
+    assert_eq!(AirportCode::from_bytes(b"\xcd\xcf\xd7"), Err(ParseError::InvalidLetter { ch: 'м', index: 0 }));
+    assert_eq!(CityCode::from_bytes(b"\xed\xb3\xf7"), Err(ParseError::InvalidLetter { ch: 'Ё', index: 1 }));
+    assert!(AirportCode::try_from(&b"\xcd\xcf\xd7"[..]).is_err());
+    assert!(bincode::deserialize::<AirportCode>(b"\xcd\xcf\xd7").is_err());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    let code = AircraftCode::from_str("ПУ1").unwrap();
+    assert_eq!(AircraftCode::try_from(code.as_bytes()).unwrap(), code);
+    match AirlineCode::try_from(&b"ABC"[..]) {
+        Err(ParseError::InvalidLength { got: 3, expected: 2 }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_try_from_str() {
+    use core::convert::TryInto;
+
+    let code: CityCode = String::from("ЛЕД").try_into().unwrap();
+    assert_eq!(code, CityCode::try_from("ЛЕД").unwrap());
+    assert!(CityCode::try_from("ЛЕДД").is_err());
+}
+
+#[test]
+fn test_serialize() {
+    let code = AirportCode::from_str("МОВ").unwrap();
+    assert_eq!(serde_json::to_string(&code).unwrap(), "\"МОВ\"");
+    assert_eq!(bincode::serialize(&code).unwrap(), code.as_bytes());
+}
+
+#[test]
+fn test_deserialize() {
+    let code = AircraftCode::from_str("ПУ1").unwrap();
+    assert_eq!(serde_json::from_str::<AircraftCode>("\"ПУ1\"").unwrap(), code);
+    assert!(serde_json::from_str::<AircraftCode>("\"ПУ\"").is_err());
+    let bytes = bincode::serialize(&code).unwrap();
+    assert_eq!(bincode::deserialize::<AircraftCode>(&bytes).unwrap(), code);
+}
+
+#[test]
+fn test_as_ref() {
+    fn len<T: AsRef<[u8]>>(value: T) -> usize {
+        value.as_ref().len()
+    }
+
+    let code = AirlineCode::from_str("СУ").unwrap();
+    assert_eq!(code.as_ref(), code.as_bytes());
+    assert_eq!(len(code), 2);
+}
+
+#[test]
+fn test_eq_str() {
+    let code = AirportCode::from_str("МОВ").unwrap();
+    assert_eq!(code, "МОВ");
+    assert_eq!("МОВ", code);
+    assert!(code != "ЛЕД");
+    assert!(*"МО" != code);
+}
+
+#[test]
+fn test_to_latin() {
+    assert_eq!(CityCode::from_str("МОВ").unwrap().to_latin(), Some("MOW"));
+    assert_eq!(AirportCode::from_str("ШРМ").unwrap().to_latin(), Some("SVO"));
+    assert_eq!(AirportCode::from_str("МОВ").unwrap().to_latin(), None);
+}
+
+#[test]
+fn test_from_latin() {
+    assert_eq!(CityCode::from_latin("mow"), Some(CityCode::from_str("МОВ").unwrap()));
+    assert_eq!(AirportCode::from_latin("LED"), Some(AirportCode::from_str("ПЛК").unwrap()));
+    assert_eq!(AirportCode::from_latin("XXX"), None);
+}
+
+#[test]
+fn test_lowercase() {
+    assert_eq!(AirportCode::from_str("мов").unwrap(), AirportCode::from_str("МОВ").unwrap());
+    assert_eq!(AirlineCode::from_str("с7").unwrap(), "С7");
+}
+
+#[test]
+fn test_yo() {
+    let code = CityCode::from_str("ЁЛЁ").unwrap();
+    assert_eq!(code, "ЕЛЕ");
+    assert_eq!(code, CityCode::from_str("ёлё").unwrap());
+    assert_eq!(CityCode::from_str(&code.to_string()).unwrap(), code);
+}
+
+#[test]
+fn test_sirena_code() {
+    fn describe<C: SirenaCode>(code: &C) -> String {
+        format!("{}/{}/{}", code.as_str(), code.as_bytes().len(), C::LEN)
+    }
+
+    assert_eq!(describe(&AirlineCode::from_str("СУ").unwrap()), "СУ/2/2");
+    assert_eq!(describe(&CityCode::from_str("МОВ").unwrap()), "МОВ/3/3");
+}
+
+#[test]
+fn test_booking_class() {
+    assert_eq!(BookingClass::from_str("Ю").unwrap(), "Ю");
+    assert_eq!(BookingClass::from_str("Y").unwrap().as_bytes(), b"Y");
+    assert!(BookingClass::from_str("1").is_err());
+    assert!(BookingClass::from_str("ЮБ").is_err());
+}
+
+#[test]
+fn test_country_code() {
+    let code = CountryCode::from_str("РФ").unwrap();
+    assert_eq!(code, "РФ");
+    assert_eq!(CountryCode::from_bytes(code.as_bytes()).unwrap(), code);
+    assert_eq!(CountryCode::from_str("Р1"), Err(ParseError::InvalidLetter { ch: '1', index: 1 }));
+}
+
+#[test]
+fn test_record_locator() {
+    let pnr = RecordLocator::from_str("0ЖЛ4ЦБ").unwrap();
+    assert_eq!(pnr.to_string(), "0ЖЛ4ЦБ");
+    assert_eq!(RecordLocator::from_bytes(pnr.as_bytes()).unwrap(), pnr);
+    assert!(RecordLocator::from_str("0ЖЛ4Ц").is_err());
+}
+
+#[test]
+fn test_into_string() {
+    let s: String = AirportCode::from_str("ВНК").unwrap().into();
+    assert_eq!(s, "ВНК");
+}
+
+#[test]
+fn test_hash_after_normalization() {
+    use std::collections::hash_map::DefaultHasher;
+    use core::hash::{Hash, Hasher};
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    for &(a, b) in &[("МОВ", "мов"), ("ЛЕД", "лЕд"), ("ЕЛЕ", "ёлё")] {
+        let a = CityCode::from_str(a).unwrap();
+        let b = CityCode::from_str(b).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+    }
+    let a = AirlineCode::from_str("С7").unwrap();
+    let b = AirlineCode::from_str("с7").unwrap();
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+}
+
+#[test]
+fn test_from_bytes_array() {
+    const MOW: CityCode = CityCode::from_bytes_array([0xED, 0xEF, 0xF7]);
+    assert_eq!(MOW, "МОВ");
+    assert_eq!(AirlineCode::from_bytes_array([0xF3, 0xF5]), "СУ");
+}
+
+#[test]
+fn test_default() {
+    let code = AirportCode::default();
+    assert_eq!(code, "   ");
+    assert!(AirportCode::from_bytes(code.as_bytes()).is_err());
+
+    #[derive(Default, Serialize, Deserialize, PartialEq, Debug)]
+    struct Segment {
+        airport: AirportCode,
+        city: Option<CityCode>,
+    }
+
+    // the blank default doesn't deserialize, Option does
+    let json = serde_json::to_string(&Segment::default()).unwrap();
+    assert_eq!(json, r#"{"airport":"   ","city":null}"#);
+    assert_eq!(serde_json::from_str::<Segment>(&json).unwrap_err().to_string(), "empty input at line 1 column 16");
+    assert!(bincode::deserialize::<Segment>(&bincode::serialize(&Segment::default()).unwrap()).is_err());
+    let segment = Segment { airport: "ШРМ".parse().unwrap(), city: None };
+    assert_eq!(serde_json::from_str::<Segment>(&serde_json::to_string(&segment).unwrap()).unwrap(), segment);
+}
+
+#[test]
+fn test_bom_like_bytes() {
+    let code = AirlineCode::from_str("ЧЪ").unwrap();
+    assert_eq!(code.as_str(), "ЧЪ");
+}
+
+#[test]
+fn test_round_trip_all() {
+    use core::fmt::Debug;
+
+    fn check<T>(len: u32, from_bytes: fn(&[u8]) -> Result<T, ParseError>)
+        where T: FromStr + SirenaCode + fmt::Display + PartialEq + Debug, T::Err: Debug
+    {
+        let alphabet: Vec<char> = ('А'..='Я').chain('0'..='9').collect();
+        let n = alphabet.len();
+        for i in 0..n.pow(len) {
+            let s: String = (0..len).map(|k| alphabet[i / n.pow(k) % n]).collect();
+            if let Ok(code) = s.parse::<T>() {
+                assert_eq!(code.to_string().parse::<T>().unwrap(), code);
+                assert_eq!(from_bytes(code.as_bytes()).unwrap(), code);
+                assert_eq!(code.as_str(), s);
+            }
+        }
+    }
+
+    check(3, AircraftCode::from_bytes);
+    check(2, AirlineCode::from_bytes);
+    check(3, AirportCode::from_bytes);
+    check(3, CityCode::from_bytes);
+}
+
+#[test]
+fn test_invalid_length_counts_chars() {
+    let err = AirportCode::from_str("МОВЛ").unwrap_err();
+    assert_eq!(err, ParseError::InvalidLength { got: 4, expected: 3 });
+    assert_eq!(err.to_string(), "invalid length 4, expected 3");
+}
+
+#[test]
+fn test_encoder_substitution() {
+    // € passes this validator but has no KOI8-R mapping
+    match CyrillicCode::<3>::parse("АБ€", |_| true) {
+        Err(e) => assert_eq!(e, ParseError::InvalidLetter { ch: '€', index: 2 }),
+        Ok(_) => panic!("unencodable character accepted"),
+    }
+}
+
+#[test]
+fn test_borrow_bytes() {
+    use std::collections::HashMap;
+
+    let code = AirportCode::from_str("ДМД").unwrap();
+    let mut map = HashMap::new();
+    map.insert(code, "Домодедово");
+    let wire = code.as_bytes().to_vec();
+    assert_eq!(map.get(&wire[..]), Some(&"Домодедово"));
+}
+
+#[test]
+fn test_chars() {
+    let code = AircraftCode::from_str("ПУ1").unwrap();
+    assert_eq!(code.chars().collect::<Vec<_>>(), ['П', 'У', '1']);
+}
+
+#[test]
+fn test_currency_code() {
+    assert_eq!(CurrencyCode::from_str("РУБ").unwrap(), "РУБ");
+    assert_eq!(CurrencyCode::from_str("EUR").unwrap().as_bytes(), b"EUR");
+    assert!(CurrencyCode::from_str("US1").is_err());
+}
+
+#[test]
+fn test_len() {
+    let wire = b"\xf3\xf5\xed\xef\xf7";
+    let (airline, city) = wire.split_at(AirlineCode::LEN);
+    assert_eq!(AirlineCode::from_bytes(airline).unwrap().len(), 2);
+    assert_eq!(CityCode::from_bytes(city).unwrap(), "МОВ");
+    assert_eq!(CityCode::LEN, <CityCode as SirenaCode>::LEN);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_to() {
+    let mut buf = Vec::new();
+    AirlineCode::from_str("СУ").unwrap().write_to(&mut buf).unwrap();
+    CityCode::from_str("МОВ").unwrap().write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"\xf3\xf5\xed\xef\xf7");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_reader() {
+    let mut wire = &b"\xf3\xf5\xed\xef\xf7"[..];
+    assert_eq!(AirlineCode::from_reader(&mut wire).unwrap().unwrap(), "СУ");
+    assert_eq!(CityCode::from_reader(&mut wire).unwrap().unwrap(), "МОВ");
+    assert!(CityCode::from_reader(&mut wire).is_err());
+    assert!(AirlineCode::from_reader(&mut &b"12"[..]).unwrap().is_err());
+}
+
+#[test]
+fn test_airline_shape() {
+    let s7 = AirlineCode::from_str("С7").unwrap();
+    let su = AirlineCode::from_str("СУ").unwrap();
+    assert!(s7.has_digit() && !s7.is_alpha());
+    assert!(su.is_alpha() && !su.has_digit());
+    assert_eq!(s7.digit(), Some(7));
+    assert_eq!(su.digit(), None);
+}
+
+#[test]
+fn test_airport_city() {
+    let moscow = CityCode::from_str("МОВ").unwrap();
+    assert_eq!(AirportCode::from_str("ВНК").unwrap().city(), Some(moscow));
+    assert_eq!(AirportCode::from_str("ПЛК").unwrap().city(), CityCode::from_latin("LED"));
+    assert_eq!(AirportCode::from_str("ЮЮЮ").unwrap().city(), None);
+}
+
+#[test]
+fn test_join_codes() {
+    let codes: Vec<CityCode> = ["МОВ", "ЛЕД", "ОВБ"].iter().map(|c| c.parse().unwrap()).collect();
+    assert_eq!(join_codes(&codes, "-"), "МОВ-ЛЕД-ОВБ");
+    assert_eq!(join_codes(&codes[..0], "-"), "");
+}
+
+#[test]
+fn test_from_str_lenient() {
+    assert_eq!(AirportCode::from_str_lenient(" мов\t").unwrap(), "МОВ");
+    assert_eq!(CurrencyCode::from_str_lenient("eur").unwrap(), "EUR");
+    assert!(AirportCode::from_str(" МОВ").is_err());
+    assert!(CurrencyCode::from_str("eur").is_err());
+}
+
+#[test]
+fn test_with_str() {
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert_eq!(airport.with_str(|s| String::from(s)), airport.as_str());
+    assert_eq!(CurrencyCode::from_str("EUR").unwrap().with_str(str::len), 3);
+    assert!(CityCode::default().with_str(|s| s == "   "));
+}
+
+#[test]
+fn test_display_alternate() {
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert_eq!(format!("{}", airport), "ШРМ");
+    assert_eq!(format!("{:#}", airport), "SVO");
+    assert_eq!(format!("{:#}", CityCode::from_str("МОВ").unwrap()), "MOW");
+    assert_eq!(format!("{:#}", CityCode::from_str("ЮЮЮ").unwrap()), "ЮЮЮ");
+    assert_eq!(format!("{:#?}", airport), "ШРМ");
+}
+
+#[test]
+fn test_aircraft_shape() {
+    let pu1 = AircraftCode::from_str("ПУ1").unwrap();
+    assert!(!pu1.is_letters_only());
+    assert_eq!(pu1.trailing_number(), Some(1));
+    assert_eq!(AircraftCode::from_str("Т15").unwrap().trailing_number(), Some(15));
+    assert!(AircraftCode::from_str("ТУШ").unwrap().is_letters_only());
+    assert_eq!(AircraftCode::from_str("ТУШ").unwrap().trailing_number(), None);
+    assert_eq!(AircraftCode::from_str("320").unwrap().trailing_number(), None);
+}
+
+#[test]
+fn test_try_from_array() {
+    assert_eq!(AirportCode::try_from(*b"\xfb\xf2\xed").unwrap(), "ШРМ");
+    assert_eq!(AirlineCode::try_from(*b"\xf3\xf5").unwrap(), "СУ");
+    assert!(AirportCode::try_from(*b"SVO").is_err());
+}
+
+#[test]
+fn test_cmp_alpha() {
+    let mut codes: Vec<CityCode> = ["ВВО", "ЯЯЯ", "БАК", "АБА"].iter().map(|c| c.parse().unwrap()).collect();
+    codes.sort();
+    assert_eq!(codes, ["АБА", "БАК", "ЯЯЯ", "ВВО"]);
+    codes.sort_by(CityCode::cmp_alpha);
+    assert_eq!(codes, ["АБА", "БАК", "ВВО", "ЯЯЯ"]);
+}
+
+#[test]
+fn test_with_char() {
+    let code = AirportCode::from_str("ШРН").unwrap();
+    assert_eq!(code.with_char(2, 'м').unwrap(), "ШРМ");
+    assert_eq!(code.with_char(0, 'S'), Err(ParseError::InvalidLetter { ch: 'S', index: 0 }));
+    assert_eq!(code.with_char(3, 'М'), Err(ParseError::IndexOutOfRange { index: 3, len: 3 }));
+    assert_eq!(AirlineCode::from_str("С7").unwrap().with_char(0, '5'), Err(ParseError::TooManyDigits(2)));
+}
+
+#[test]
+fn test_ssr_code() {
+    assert_eq!(SsrCode::from_str("WCHR").unwrap(), "WCHR");
+    assert_eq!(SsrCode::from_str("ВГМЛ").unwrap().as_bytes().len(), 4);
+    assert!(SsrCode::from_str("WCH").is_err());
+    assert!(SsrCode::from_str("WCH1").is_err());
+    assert_eq!(serde_json::to_string(&SsrCode::from_str("VGML").unwrap()).unwrap(), "\"VGML\"");
+}
+
+#[cfg(feature = "airport-names")]
+#[test]
+fn test_airport_name() {
+    assert_eq!(AirportCode::from_str("ПЛК").unwrap().name(), Some("Санкт-Петербург Пулково"));
+    assert_eq!(AirportCode::from_str("ТЛЧ").unwrap().name(), Some("Новосибирск Толмачёво"));
+    assert_eq!(AirportCode::from_str("КУФ").unwrap().name(), Some("Самара Курумоч"));
+    assert_eq!(AirportCode::from_str("ТШК").unwrap().name(), Some("Ташкент Ислам Каримов"));
+    assert_eq!(AirportCode::from_str("ТЛЧ").unwrap().city().unwrap(), "ОВБ");
+    assert_eq!(AirportCode::from_str("ЮЮЮ").unwrap().name(), None);
+}
+
+#[test]
+fn test_aircraft_strict() {
+    assert_eq!(AircraftCode::from_str_strict("123"), Err(ParseError::NoLetters));
+    assert!(AircraftCode::from_str("123").is_ok());
+    assert_eq!(AircraftCode::from_str_strict("ПУ1").unwrap(), "ПУ1");
+    assert_eq!(AircraftCode::from_str_strict("АБВ").unwrap(), "АБВ");
+}
+
+#[test]
+fn test_from_str_verbose() {
+    let err = AirportCode::from_str_verbose("МО1").unwrap_err();
+    assert_eq!(err.input(), "МО1");
+    assert_eq!(err.error(), ParseError::InvalidLetter { ch: '1', index: 2 });
+    assert_eq!(err.to_string(), "failed to parse \"МО1\" as AirportCode: invalid character 1 at position 2");
+    assert_eq!(CityCode::from_str_verbose("МОВ").unwrap(), "МОВ");
+}
+
+#[test]
+fn test_to_ascii() {
+    assert_eq!(AirportCode::from_str("ШРМ").unwrap().to_ascii(), "SHRM");
+    assert_eq!(CityCode::from_str("ЩЮЯ").unwrap().to_ascii(), "SHCHIUIA");
+    assert_eq!(AirlineCode::from_str("С7").unwrap().to_ascii(), "S7");
+    assert_eq!(AircraftCode::from_str("ТУ5").unwrap().to_ascii(), "TU5");
+}
+
+#[test]
+fn test_encode_into() {
+    let mut frame = [0; 8];
+    assert_eq!(AirportCode::encode_into("шрм", &mut frame), Ok(3));
+    assert_eq!(&frame[..3], b"\xfb\xf2\xed");
+    assert_eq!(AirlineCode::encode_into("СУ", &mut frame[3..]), Ok(2));
+    assert_eq!(AirportCode::encode_into("ШРМ", &mut frame[..2]), Err(ParseError::BufferTooSmall { needed: 3, got: 2 }));
+    assert_eq!(AirportCode::encode_into("SVO", &mut frame), Err(ParseError::InvalidLetter { ch: 'S', index: 0 }));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_boxed_error() {
+    use std::error::Error;
+
+    fn parse_aircraft(value: &str) -> Result<AircraftCode, Box<dyn Error>> {
+        Ok(value.parse()?)
+    }
+
+    fn parse_flight(value: &str) -> Result<FlightNumber, Box<dyn Error + Send + Sync>> {
+        Ok(value.parse()?)
+    }
+
+    assert_eq!(parse_aircraft("ПУ1").unwrap(), "ПУ1");
+    assert_eq!(parse_aircraft("ПУ!").unwrap_err().to_string(), "invalid character ! at position 2");
+    let err = parse_flight("S71234").unwrap_err();
+    assert_eq!(err.to_string(), "invalid airline code: invalid character S at position 0");
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn test_single_airport_of() {
+    let city = |c: &str| CityCode::from_str(c).unwrap();
+    assert_eq!(AirportCode::single_airport_of(city("СПТ")).unwrap(), "ПЛК");
+    assert_eq!(AirportCode::single_airport_of(city("МОВ")), None);
+    assert_eq!(AirportCode::single_airport_of(city("ЮЮЮ")), None);
+}
+
+#[test]
+fn test_airports_of() {
+    let moscow = AirportCode::airports_of(CityCode::from_str("МОВ").unwrap());
+    assert_eq!(moscow, ["ВНК", "ДМД", "ЖКВ", "ШРМ"].iter().map(|c| c.parse().unwrap()).collect::<Vec<AirportCode>>());
+    assert_eq!(AirportCode::airports_of(CityCode::from_str("ЕКБ").unwrap()), [AirportCode::from_str("КЛЦ").unwrap()]);
+    assert!(AirportCode::airports_of(CityCode::from_str("ЮЮЮ").unwrap()).is_empty());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_json_schema() {
+    let schema = serde_json::to_value(schemars::schema_for!(AirlineCode)).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["maxLength"], 2);
+    assert_eq!(schema["pattern"], "^([А-Я][А-Я0-9]|[0-9][А-Я])$");
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_enum_json_schemas() {
+    let schema = serde_json::to_value(schemars::schema_for!(Gender)).unwrap();
+    assert_eq!(schema["pattern"], "^[МЖ]$");
+    let schema = serde_json::to_value(schemars::schema_for!(PassengerType)).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["maxLength"], 2);
+    let schema = serde_json::to_value(schemars::schema_for!(SegmentStatus)).unwrap();
+    assert_eq!(schema["pattern"], "^([А-Я]{2}|[A-Z]{2})$");
+}
+
+#[test]
+fn test_parse_all() {
+    let parsed = parse_all::<CityCode>(&["МОВ", "LED", "ОВБ"]);
+    assert_eq!(parsed.len(), 3);
+    assert_eq!(parsed[0], Ok(CityCode::from_str("МОВ").unwrap()));
+    assert_eq!(parsed[1], Err(ParseError::InvalidLetter { ch: 'L', index: 0 }));
+    let failed: Vec<usize> = parsed.iter().enumerate().filter(|(_, r)| r.is_err()).map(|(i, _)| i).collect();
+    assert_eq!(failed, [1]);
+}
+
+#[test]
+fn test_as_str_borrows_ascii() {
+    assert!(matches!(AircraftCode::from_str("320").unwrap().as_str(), Cow::Borrowed("320")));
+    assert!(matches!(CurrencyCode::from_str("EUR").unwrap().as_str(), Cow::Borrowed("EUR")));
+    assert_eq!(AirportCode::from_str("ШРМ").unwrap().as_str(), "ШРМ");
+}
+
+#[test]
+fn test_into_byte_array() {
+    let bytes: [u8; 3] = AirportCode::from_str("ШРМ").unwrap().into();
+    assert_eq!(&bytes, b"\xfb\xf2\xed");
+    assert_eq!(<[u8; 2]>::from(AirlineCode::from_str("С7").unwrap()), *b"\xf37");
+}
+
+#[test]
+fn test_as_byte_array() {
+    let airline = AirlineCode::from_str("СУ").unwrap();
+    let bytes: &[u8; 2] = airline.as_byte_array();
+    assert_eq!(bytes, b"\xf3\xf5");
+    assert_eq!(AirportCode::from_str("ШРМ").unwrap().as_byte_array().len(), 3);
+}
+
+#[test]
+fn test_latin_confusables() {
+    // Latin M, O and B
+    assert_eq!(CityCode::from_str_lenient("MOB").unwrap(), "МОВ");
+    assert_eq!(CityCode::from_str_lenient("мoв").unwrap(), "МОВ");
+    assert_eq!(CurrencyCode::from_str_lenient("eur").unwrap().as_bytes(), b"EUR");
+    assert!(CityCode::from_str_lenient("LED").is_err());
+    let err = CityCode::from_str("MOВ").unwrap_err();
+    assert_eq!(err.to_string(),
+               "invalid character M at position 0, looks like Latin M, did you mean Cyrillic М?");
+    assert_eq!(SsrCode::from_str("WCH1").unwrap_err().to_string(), "invalid character 1 at position 3");
+}
+
+#[test]
+fn test_office_code() {
+    let office = OfficeCode::from_str("мов001").unwrap();
+    assert_eq!(office, "МОВ001");
+    assert_eq!(office.as_bytes().len(), 6);
+    assert_eq!(OfficeCode::from_str("МОВ01"), Err(ParseError::InvalidLength { got: 5, expected: 6 }));
+    assert!(OfficeCode::from_str("MOW001").is_err());
+    assert_eq!(serde_json::to_string(&office).unwrap(), "\"МОВ001\"");
+}
+
+#[test]
+fn test_same_code_as() {
+    let city = CityCode::from_str("СОЧ").unwrap();
+    assert!(AirportCode::from_str("СОЧ").unwrap().same_code_as(&city));
+    assert!(!AirportCode::from_str("ШРМ").unwrap().same_code_as(&CityCode::from_str("МОВ").unwrap()));
+}
+
+#[test]
+fn test_display_padding() {
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert_eq!(format!("{:<5}|", airport), "ШРМ  |");
+    assert_eq!(format!("{:>5}|", airport), "  ШРМ|");
+    assert_eq!(format!("{:*^7}", airport), "**ШРМ**");
+    assert_eq!(format!("{:.2}", airport), "ШР");
+    assert_eq!(format!("{:#5}|", airport), "SVO  |");
+    assert_eq!(format!("{:4}|", AirlineCode::from_str("СУ").unwrap()), "СУ  |");
+}
+
+#[test]
+fn test_try_as_str() {
+    assert_eq!(AirportCode::from_str("ШРМ").unwrap().try_as_str().unwrap(), "ШРМ");
+    let corrupted = AirportCode::from_bytes_array(*b"\xfb\x00\xed");
+    assert_eq!(corrupted.try_as_str().unwrap_err().error(), ParseError::InvalidLetter { ch: '\0', index: 1 });
+    assert_eq!(corrupted.as_str(), "Ш\0М");
+    assert!(AirlineCode::from_bytes_array(*b"77").try_as_str().is_err());
+    // lowercase мов decodes fine but no parsed code has these bytes
+    let lowercase = AirportCode::from_bytes_array(*b"\xcd\xcf\xd7");
+    assert_eq!(lowercase.try_as_str().unwrap_err().error(), ParseError::InvalidLetter { ch: 'м', index: 0 });
+}
+
+#[test]
+fn test_code_literals() {
+    const SVO: AirportCode = airport_code!("ШРМ");
+    assert_eq!(SVO, AirportCode::from_str("ШРМ").unwrap());
+    assert_eq!(airline_code!("С7"), AirlineCode::from_str("С7").unwrap());
+    assert_eq!(aircraft_code!("320"), "320");
+    assert_eq!(currency_code!("EUR"), "EUR");
+    assert_eq!(ssr_code!("WCHR"), "WCHR");
+    assert_eq!(office_code!("МОВ001"), "МОВ001");
+    assert_eq!(city_code!("МОВ"), "МОВ");
+    assert_eq!(country_code!("РФ"), "РФ");
+    assert_eq!(booking_class!("Y"), "Y");
+    assert_eq!(record_locator!("АБ12В3"), "АБ12В3");
+}
+
+#[test]
+fn test_is_valid() {
+    assert!(AirportCode::is_valid("шрм"));
+    assert!(!AirportCode::is_valid("SVO"));
+    assert!(!AirportCode::is_valid("ШРМШ"));
+    assert!(AirlineCode::is_valid("С7"));
+    assert!(!AirlineCode::is_valid("77"));
+    assert!(CurrencyCode::is_valid("EUR"));
+    for code in &["ПУ1", "ПУ!", "1", "АБВГ"] {
+        assert_eq!(AircraftCode::is_valid(code), code.parse::<AircraftCode>().is_ok());
+    }
+}
+
+#[test]
+fn test_from_bytes_too_long() {
+    assert_eq!(AirportCode::from_bytes(b"\xfb\xf2\xed\xed"), Err(ParseError::InvalidLength { got: 4, expected: 3 }));
+    assert_eq!(AirlineCode::from_bytes(&[0xf3; 64]), Err(ParseError::InvalidLength { got: 64, expected: 2 }));
+    assert_eq!(CityCode::from_bytes(b""), Err(ParseError::InvalidLength { got: 0, expected: 3 }));
+}
+
+#[test]
+fn test_hash_stable() {
+    /// FNV-1a, with lengths written as little endian u64 to be platform independent
+    struct Fnv(u64);
+
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100_0000_01b3);
+            }
+        }
+
+        fn write_usize(&mut self, i: usize) {
+            self.write(&(i as u64).to_le_bytes())
+        }
+    }
+
+    fn fnv<T: Hash>(value: &T) -> u64 {
+        let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert_eq!(fnv(&airport), fnv(&&b"\xfb\xf2\xed"[..]));
+    assert_eq!(fnv(&airport), 0x2da3_42d0_4bd9_a986);
+}
+
+#[test]
+fn test_meal_code() {
+    let meal = MealCode::from_str("VGML").unwrap();
+    assert_eq!(meal, "VGML");
+    assert_eq!(meal.as_bytes(), b"VGML");
+    assert!(MealCode::from_str("VG").is_err());
+    assert_eq!(meal_code!("VGML"), meal);
+    assert_eq!(serde_json::from_str::<MealCode>("\"KSML\"").unwrap(), "KSML");
+    assert!(meal.is_standard());
+    assert!(MEALS.windows(2).all(|w| w[0] < w[1]));
+    let wheelchair = MealCode::from_str("WCHR").unwrap();
+    assert!(!wheelchair.is_standard());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_rkyv() {
+    let airports: Vec<AirportCode> = ["ШРМ", "ВНК"].iter().map(|c| c.parse().unwrap()).collect();
+    let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&airports).unwrap();
+    let archived = rkyv::access::<rkyv::Archived<Vec<AirportCode>>, rkyv::rancor::Error>(&bytes).unwrap();
+    // archived codes are the KOI8-R bytes, compared without deserializing
+    assert!(archived[0] == airports[0]);
+    // utf8-storage archives the decoded form after each code
+    #[cfg(not(feature = "utf8-storage"))]
+    assert!(bytes.starts_with(b"\xfb\xf2\xed\xf7\xee\xeb"));
+    let back: Vec<AirportCode> = rkyv::deserialize::<_, rkyv::rancor::Error>(archived).unwrap();
+    assert_eq!(back, airports);
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!("".parse::<AirportCode>(), Err(ParseError::Empty));
+    assert_eq!("   ".parse::<AirportCode>(), Err(ParseError::Empty));
+    assert_eq!("".parse::<AirlineCode>(), Err(ParseError::Empty));
+    assert_eq!("\t".parse::<CityCode>(), Err(ParseError::Empty));
+    assert_eq!(" ".parse::<AircraftCode>(), Err(ParseError::Empty));
+    // surrounding whitespace alone doesn't make it empty
+    assert_eq!(" М ".parse::<AirportCode>(), Err(ParseError::InvalidLetter { ch: ' ', index: 0 }));
+}
+
+#[test]
+fn test_byte_and_char_len() {
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert_eq!(airport.byte_len(), 3);
+    assert_eq!(airport.char_len(), 3);
+    assert_eq!(airport.as_str().len(), 6);
+    assert_eq!(RecordLocator::from_str("АБ12В3").unwrap().char_len(), 6);
+    assert_eq!(AirlineCode::default().byte_len(), AirlineCode::LEN);
+}
+
+#[test]
+fn test_from_translit() {
+    assert_eq!(AirportCode::from_translit("IHV").unwrap(), "ШРМ");
+    assert_eq!(AirportCode::from_translit("dyr").unwrap(), "ВНК");
+    assert_eq!(CityCode::from_translit("CG<").unwrap(), "СПБ");
+    assert_eq!(AirlineCode::from_translit("C7").unwrap(), "С7");
+    assert_eq!(AirportCode::from_translit("IH!"), Err(ParseError::InvalidLetter { ch: '!', index: 2 }));
+}
+
+#[test]
+fn test_serde_embedded() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Leg {
+        from: AirportCode,
+        to: AirportCode,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(transparent)]
+    struct Carrier(AirlineCode);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Segment {
+        carrier: Carrier,
+        #[serde(flatten)]
+        leg: Leg,
+    }
+
+    let leg = Leg { from: "ШРМ".parse().unwrap(), to: "ПЛК".parse().unwrap() };
+    let json = serde_json::to_string(&leg).unwrap();
+    assert_eq!(json, r#"{"from":"ШРМ","to":"ПЛК"}"#);
+    assert_eq!(serde_json::from_str::<Leg>(&json).unwrap(), leg);
+    let bytes = bincode::serialize(&leg).unwrap();
+    assert_eq!(bytes, b"\xfb\xf2\xed\xf0\xec\xeb");
+    assert_eq!(bincode::deserialize::<Leg>(&bytes).unwrap(), leg);
+
+    let segment = Segment { carrier: Carrier("СУ".parse().unwrap()), leg };
+    let json = serde_json::to_string(&segment).unwrap();
+    assert_eq!(json, r#"{"carrier":"СУ","from":"ШРМ","to":"ПЛК"}"#);
+    assert_eq!(serde_json::from_str::<Segment>(&json).unwrap(), segment);
+}
+
+#[test]
+fn test_split_pair() {
+    let (from, to): (CityCode, CityCode) = split_pair("МОВЛЕД").unwrap();
+    assert_eq!((from, to), (CityCode::from_str("МОВ").unwrap(), CityCode::from_str("ЛЕД").unwrap()));
+    let (from, to) = split_pair::<AirportCode>("ШРМПЛК").unwrap();
+    assert_eq!(join_codes(&[from, to], "-"), "ШРМ-ПЛК");
+    assert_eq!(split_pair::<CityCode>("МОВЛЕДО"), Err(ParseError::InvalidLength { got: 4, expected: 3 }));
+    assert_eq!(split_pair::<CityCode>("М1ВЛЕД"), Err(ParseError::InvalidLetter { ch: '1', index: 1 }));
+    assert_eq!(split_pair::<CityCode>(""), Err(ParseError::Empty));
+}
+
+#[test]
+fn test_into_iter() {
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    let mut bytes = Vec::new();
+    for b in &airport {
+        bytes.push(b);
+    }
+    assert_eq!(bytes, airport.as_bytes());
+    assert_eq!((&AirlineCode::from_str("С7").unwrap()).into_iter().last(), Some(b'7'));
+}
+
+#[test]
+fn test_from_bytes_control() {
+    assert_eq!(AirportCode::from_bytes(&[0, 0, 0]), Err(ParseError::InvalidLetter { ch: '\0', index: 0 }));
+    assert_eq!(AirlineCode::from_bytes(b"\xf3\x00"), Err(ParseError::InvalidLetter { ch: '\0', index: 1 }));
+    assert_eq!(CurrencyCode::from_bytes(b"EU\n"), Err(ParseError::InvalidLetter { ch: '\n', index: 2 }));
+    assert_eq!(CurrencyCode::from_bytes(b"EU\x7f"), Err(ParseError::InvalidLetter { ch: '\x7f', index: 2 }));
+    assert_eq!(AirportCode::from_bytes(&[0, 0, 0]).unwrap_err().to_string(), "invalid character \\0 at position 0");
+    assert_eq!(CurrencyCode::from_bytes(b"EU\n").unwrap_err().to_string(), "invalid character \\n at position 2");
+}
+
+#[test]
+fn test_expected_len() {
+    fn check<T: SirenaCode + FromStr<Err = ParseError>>() {
+        let err = "Ж".repeat(T::LEN + 1).parse::<T>().err().unwrap();
+        assert_eq!(err.expected_len(), Some(T::LEN));
+        assert!(err.to_string().ends_with(&format!("expected {}", T::LEN)));
+    }
+    check::<AircraftCode>();
+    check::<AirlineCode>();
+    check::<AirportCode>();
+    check::<CityCode>();
+    check::<BookingClass>();
+    check::<RecordLocator>();
+    check::<OfficeCode>();
+    assert_eq!(ParseError::Empty.expected_len(), None);
+    let err = "ABC".parse::<TerminalCode>().unwrap_err();
+    assert_eq!(err.expected_len(), None);
+    assert_eq!(err.max_len(), Some(2));
+    assert_eq!(err.to_string(), "invalid length 3, expected at most 2");
+}
+
+#[cfg(feature = "utf8-storage")]
+#[test]
+fn test_utf8_storage() {
+    for b in 0x80..=0xffu8 {
+        assert_eq!(decode_byte(b), KOI8_UPPER[(b - 0x80) as usize]);
+    }
+    let airport = AirportCode::from_str("ШРМ").unwrap();
+    assert!(matches!(airport.as_str(), Cow::Borrowed("ШРМ")));
+    assert_eq!(airport.as_bytes(), b"\xfb\xf2\xed");
+    const VNUKOVO: AirportCode = airport_code!("ВНК");
+    assert!(matches!(VNUKOVO.as_str(), Cow::Borrowed("ВНК")));
+    assert_eq!(airport_code!("ШРМ"), airport);
+    assert!(matches!(AirlineCode::default().as_str(), Cow::Borrowed("  ")));
+}
+
+#[test]
+fn test_is_code_char() {
+    assert!(is_code_letter('Ш') && is_code_letter('ш') && is_code_letter('Ё'));
+    assert!(!is_code_letter('7') && !is_code_letter('A') && !is_code_letter(' '));
+    assert!(is_code_char('7') && is_code_char('ё'));
+    assert!(!is_code_char('-'));
+    // same rules as the parsers
+    for input in &["шрм", "ЁКБ", "ШР1", "ШRМ"] {
+        assert_eq!(input.chars().all(is_code_letter), AirportCode::from_str(input).is_ok());
+    }
+    assert!("с7".chars().all(is_code_char) && AirlineCode::from_str("с7").is_ok());
+}
+
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+#[test]
+fn test_alphabets() {
+    assert!(LETTERS.iter().all(|&c| is_letter(c)));
+    assert_eq!(LETTERS.len(), ('А'..='Я').count());
+    assert!(LETTERS_AND_DIGITS.iter().all(|&c| is_letter_or_digit(c)));
+    assert_eq!(LETTERS_AND_DIGITS.len(), LETTERS.len() + 10);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_months_exported() {
+    assert_eq!(MONTHS[0], "ЯНВ");
+    assert_eq!(MONTHS.iter().position(|&m| m == "МАЙ"), Some(4));
+}
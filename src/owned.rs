@@ -0,0 +1,225 @@
+//! Any one of the code types, with its kind known only at runtime
+
+use core::fmt;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::{AircraftCode, AirlineCode, AirportCode, BookingClass, CityCode, CountryCode, CurrencyCode, FareBasis,
+            GateCode, MealCode, OfficeCode, ParseError, RecordLocator, SsrCode, TerminalCode};
+
+macro_rules! gen_owned_code {
+    ($($kind: ident($t: ident, $min: expr, $max: expr)),*) => {
+        /// Some code, see `OwnedCode::from_str` for how the kind is picked
+        #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+        pub enum OwnedCode {
+            $($kind($t),)*
+        }
+
+        /// Kind of an `OwnedCode`
+        #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+        pub enum CodeKind {
+            $($kind,)*
+        }
+
+        impl CodeKind {
+            /// Every kind, in the order `OwnedCode::from_str` tries them
+            pub const ALL: &'static [CodeKind] = &[$(CodeKind::$kind,)*];
+
+            /// Name of the code type, like AirportCode
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $(CodeKind::$kind => stringify!($t),)*
+                }
+            }
+
+            /// Lengths of codes of this kind, a single one except for the inline codes
+            pub fn lengths(&self) -> RangeInclusive<usize> {
+                match *self {
+                    $(CodeKind::$kind => $min..=$max,)*
+                }
+            }
+
+            /// Parse `value` as a code of this kind
+            pub fn parse(&self, value: &str) -> Result<OwnedCode, ParseError> {
+                match *self {
+                    $(CodeKind::$kind => value.parse().map(OwnedCode::$kind),)*
+                }
+            }
+        }
+
+        impl OwnedCode {
+            pub fn kind(&self) -> CodeKind {
+                match *self {
+                    $(OwnedCode::$kind(_) => CodeKind::$kind,)*
+                }
+            }
+
+            pub fn as_str(&self) -> Cow<'_, str> {
+                match *self {
+                    $(OwnedCode::$kind(ref code) => code.as_str(),)*
+                }
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                match *self {
+                    $(OwnedCode::$kind(ref code) => code.as_bytes(),)*
+                }
+            }
+        }
+
+        $(
+            impl From<$t> for OwnedCode {
+                fn from(code: $t) -> Self {
+                    OwnedCode::$kind(code)
+                }
+            }
+        )*
+    }
+}
+
+// the order is the preference among kinds of the same length. The
+// variable-length inline codes come last, so they only get what no
+// fixed-length code accepts, with the fare basis as the catch-all
+gen_owned_code!(
+    Booking(BookingClass, BookingClass::LEN, BookingClass::LEN),
+    Airline(AirlineCode, AirlineCode::LEN, AirlineCode::LEN),
+    Country(CountryCode, CountryCode::LEN, CountryCode::LEN),
+    Airport(AirportCode, AirportCode::LEN, AirportCode::LEN),
+    City(CityCode, CityCode::LEN, CityCode::LEN),
+    Aircraft(AircraftCode, AircraftCode::LEN, AircraftCode::LEN),
+    Currency(CurrencyCode, CurrencyCode::LEN, CurrencyCode::LEN),
+    Ssr(SsrCode, SsrCode::LEN, SsrCode::LEN),
+    Meal(MealCode, MealCode::LEN, MealCode::LEN),
+    RecordLocator(RecordLocator, RecordLocator::LEN, RecordLocator::LEN),
+    Office(OfficeCode, OfficeCode::LEN, OfficeCode::LEN),
+    Terminal(TerminalCode, 1, TerminalCode::MAX_LEN),
+    Gate(GateCode, 1, GateCode::MAX_LEN),
+    Fare(FareBasis, 1, FareBasis::MAX_LEN)
+);
+
+impl FromStr for OwnedCode {
+    type Err = ParseError;
+
+    /// Try every kind of the input's length in `CodeKind::ALL` order, so
+    /// МОВ is an airport rather than a city and 12 a terminal rather than
+    /// a gate. Fails with the error of the first kind tried, or `TooLong`
+    /// past the longest kind.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let len = value.chars().count();
+        let mut kinds = CodeKind::ALL.iter().filter(|kind| kind.lengths().contains(&len));
+        let first = match kinds.next() {
+            Some(kind) => kind.parse(value),
+            None => {
+                // every length from 1 up is covered by some kind
+                let max = CodeKind::ALL.iter().map(|kind| *kind.lengths().end()).max().unwrap_or(0);
+                return Err(ParseError::TooLong { got: len, max });
+            }
+        };
+        first.or_else(|e| kinds.find_map(|kind| kind.parse(value).ok()).ok_or(e))
+    }
+}
+
+impl fmt::Display for OwnedCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.as_str())
+    }
+}
+
+/// A struct of the kind name and the code, so it round trips unambiguously
+impl Serialize for OwnedCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OwnedCode", 2)?;
+        state.serialize_field("kind", self.kind().name())?;
+        state.serialize_field("code", &self.as_str())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OwnedCodeVisitor;
+
+        impl<'de> Visitor<'de> for OwnedCodeVisitor {
+            type Value = OwnedCode;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a code with its kind")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<OwnedCode, A::Error> {
+                let kind: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let code: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                parse_kind(&kind, &code)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<OwnedCode, A::Error> {
+                let (mut kind, mut code) = (None, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match &*key {
+                        "kind" => kind = Some(map.next_value::<String>()?),
+                        "code" => code = Some(map.next_value::<String>()?),
+                        _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                    }
+                }
+                let kind = kind.ok_or_else(|| de::Error::missing_field("kind"))?;
+                let code = code.ok_or_else(|| de::Error::missing_field("code"))?;
+                parse_kind(&kind, &code)
+            }
+        }
+
+        fn parse_kind<E: de::Error>(kind: &str, code: &str) -> Result<OwnedCode, E> {
+            let kind = CodeKind::ALL.iter().find(|k| k.name() == kind)
+                .ok_or_else(|| E::custom(format_args!("unknown code kind {}", kind)))?;
+            kind.parse(code).map_err(E::custom)
+        }
+
+        const FIELDS: &[&str] = &["kind", "code"];
+        deserializer.deserialize_struct("OwnedCode", FIELDS, OwnedCodeVisitor)
+    }
+}
+
+#[test]
+fn test_owned_code() {
+    use serde_json;
+    use bincode;
+
+    let code: OwnedCode = "МОВ".parse().unwrap();
+    assert_eq!(code.kind(), CodeKind::Airport);
+    assert_eq!(code.to_string(), "МОВ");
+    assert_eq!("ПУ1".parse::<OwnedCode>().unwrap().kind(), CodeKind::Aircraft);
+    assert_eq!("EUR".parse::<OwnedCode>().unwrap().kind(), CodeKind::Currency);
+    assert_eq!("С7".parse::<OwnedCode>().unwrap().kind(), CodeKind::Airline);
+    assert_eq!("АБ12В3".parse::<OwnedCode>().unwrap().kind(), CodeKind::RecordLocator);
+    assert_eq!("Д".parse::<OwnedCode>().unwrap().kind(), CodeKind::Booking);
+    assert_eq!("1".parse::<OwnedCode>().unwrap().kind(), CodeKind::Terminal);
+    assert_eq!("Б12А".parse::<OwnedCode>().unwrap().kind(), CodeKind::Gate);
+    assert_eq!("МОВЛЕДО".parse::<OwnedCode>().unwrap().kind(), CodeKind::Fare);
+    assert_eq!("ЭКОНОМ1234567890".parse::<OwnedCode>(), Err(ParseError::TooLong { got: 16, max: 15 }));
+    assert_eq!("М!В".parse::<OwnedCode>(), Err(ParseError::InvalidLetter { ch: '!', index: 1 }));
+
+    let city = OwnedCode::from(CityCode::from_str("МОВ").unwrap());
+    let json = serde_json::to_string(&city).unwrap();
+    assert_eq!(json, r#"{"kind":"CityCode","code":"МОВ"}"#);
+    assert_eq!(serde_json::from_str::<OwnedCode>(&json).unwrap(), city);
+    assert!(serde_json::from_str::<OwnedCode>(r#"{"kind":"Nope","code":"МОВ"}"#).is_err());
+    let bytes = bincode::serialize(&city).unwrap();
+    assert_eq!(bincode::deserialize::<OwnedCode>(&bytes).unwrap(), city);
+
+    let fare = OwnedCode::from(FareBasis::from_str("ЦЛТОЦ").unwrap());
+    let json = serde_json::to_string(&fare).unwrap();
+    assert_eq!(json, r#"{"kind":"FareBasis","code":"ЦЛТОЦ"}"#);
+    assert_eq!(serde_json::from_str::<OwnedCode>(&json).unwrap(), fare);
+    let bytes = bincode::serialize(&fare).unwrap();
+    assert_eq!(bincode::deserialize::<OwnedCode>(&bytes).unwrap(), fare);
+}
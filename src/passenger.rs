@@ -0,0 +1,68 @@
+//! Passenger type codes
+
+use core::fmt;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::{is_letter, CyrillicCode, ParseError};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum PassengerType {
+    /// ВЗ, взрослый
+    Adult,
+    /// РБ, ребёнок
+    Child,
+    /// МЛ, младенец
+    Infant,
+    /// Any other valid code, as KOI8-R bytes
+    Other([u8; 2]),
+}
+
+impl PassengerType {
+    pub fn as_str(&self) -> Cow<'static, str> {
+        match *self {
+            PassengerType::Adult => Cow::Borrowed("ВЗ"),
+            PassengerType::Child => Cow::Borrowed("РБ"),
+            PassengerType::Infant => Cow::Borrowed("МЛ"),
+            PassengerType::Other(bytes) => Cow::Owned(CyrillicCode::new(bytes).as_str().into_owned()),
+        }
+    }
+}
+
+impl FromStr for PassengerType {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let code = CyrillicCode::<2>::parse(value, is_letter)?;
+        Ok(match &*code.as_str() {
+            "ВЗ" => PassengerType::Adult,
+            "РБ" => PassengerType::Child,
+            "МЛ" => PassengerType::Infant,
+            _ => PassengerType::Other(code.0),
+        })
+    }
+}
+
+impl fmt::Display for PassengerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.as_str())
+    }
+}
+
+gen_str_serde!(PassengerType, "a passenger type code");
+gen_str_schema!(PassengerType, 2, 2, "^[А-Я]{2}$");
+
+#[test]
+fn test_passenger_type() {
+    use serde_json;
+
+    assert_eq!("ВЗ".parse::<PassengerType>().unwrap(), PassengerType::Adult);
+    assert_eq!("мл".parse::<PassengerType>().unwrap(), PassengerType::Infant);
+    let other: PassengerType = "ШК".parse().unwrap();
+    assert_eq!(other.to_string(), "ШК");
+    assert!("В3".parse::<PassengerType>().is_err());
+    assert_eq!(serde_json::to_string(&PassengerType::Child).unwrap(), "\"РБ\"");
+    assert_eq!(serde_json::from_str::<PassengerType>("\"ШК\"").unwrap(), other);
+}
@@ -0,0 +1,48 @@
+//! `Arbitrary` impls generating only valid codes
+
+use core::fmt::Debug;
+use core::str::FromStr;
+use alloc::string::String;
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::sample::select;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use super::{AircraftCode, AirlineCode, AirportCode, CityCode, LETTERS, LETTERS_AND_DIGITS};
+
+/// Strings of `len` characters of `alphabet`, keeping those `FromStr` accepts
+fn code<T: FromStr + Debug + 'static>(len: usize, alphabet: &'static [char]) -> BoxedStrategy<T> {
+    vec(select(alphabet), len)
+        .prop_filter_map("invalid code", |chars| chars.into_iter().collect::<String>().parse().ok())
+        .boxed()
+}
+
+macro_rules! gen_arbitrary {
+    ($t: ty, $len: expr, $alphabet: expr) => {
+        impl Arbitrary for $t {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<$t>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                code($len, $alphabet)
+            }
+        }
+    }
+}
+
+gen_arbitrary!(AircraftCode, 3, LETTERS_AND_DIGITS);
+gen_arbitrary!(AirlineCode, 2, LETTERS_AND_DIGITS);
+gen_arbitrary!(AirportCode, 3, LETTERS);
+gen_arbitrary!(CityCode, 3, LETTERS);
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_arbitrary_round_trip(aircraft: AircraftCode, airline: AirlineCode, airport: AirportCode, city: CityCode) {
+        prop_assert_eq!(aircraft.as_str().parse::<AircraftCode>().unwrap(), aircraft);
+        prop_assert_eq!(airline.as_str().parse::<AirlineCode>().unwrap(), airline);
+        prop_assert_eq!(airport.as_str().parse::<AirportCode>().unwrap(), airport);
+        prop_assert_eq!(city.as_str().parse::<CityCode>().unwrap(), city);
+    }
+}
@@ -0,0 +1,88 @@
+//! Origin and destination pair, e.g. МОВЛЕД or МОВ-ЛЕД
+
+use core::fmt;
+use core::str::FromStr;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::{CityCode, ParseError};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct Route {
+    from: CityCode,
+    to: CityCode,
+}
+
+impl Route {
+    pub fn new(from: CityCode, to: CityCode) -> Self {
+        Route { from, to }
+    }
+
+    pub fn from(&self) -> CityCode {
+        self.from
+    }
+
+    pub fn to(&self) -> CityCode {
+        self.to
+    }
+
+    /// Both codes joined with `sep`, e.g. МОВ → ЛЕД
+    pub fn display_with(&self, sep: &str) -> String {
+        let (from, to) = (self.from.as_str(), self.to.as_str());
+        let mut joined = String::with_capacity(from.len() + sep.len() + to.len());
+        joined.push_str(&from);
+        joined.push_str(sep);
+        joined.push_str(&to);
+        joined
+    }
+}
+
+impl FromStr for Route {
+    type Err = ParseError;
+
+    /// Accepts both the compact МОВЛЕД and the hyphenated МОВ-ЛЕД of `Display`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let len = value.chars().count();
+        let hyphenated = len == 7 && value.chars().nth(3) == Some('-');
+        if len != 2 * CityCode::LEN && !hyphenated {
+            return Err(ParseError::InvalidLength { got: len, expected: 2 * CityCode::LEN });
+        }
+        let (split, _) = value.char_indices().nth(3).unwrap();
+        let (from, to) = value.split_at(split);
+        let (to, offset) = if hyphenated { (&to[1..], 4) } else { (to, 3) };
+        let from = from.parse()?;
+        let to = to.parse().map_err(|e| match e {
+            // position in the whole route rather than in the destination
+            ParseError::InvalidLetter { ch, index } => ParseError::InvalidLetter { ch, index: index + offset },
+            e => e,
+        })?;
+        Ok(Route::new(from, to))
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.from, self.to)
+    }
+}
+
+#[test]
+fn test_route() {
+    let route: Route = "МОВЛЕД".parse().unwrap();
+    assert_eq!(route.from(), "МОВ");
+    assert_eq!(route.to(), "ЛЕД");
+    assert_eq!(route.to_string(), "МОВ-ЛЕД");
+    assert_eq!(route.display_with(" → "), "МОВ → ЛЕД");
+    assert_eq!(route.display_with(""), "МОВЛЕД");
+    assert_eq!("МОВ-ЛЕД".parse::<Route>(), Ok(route));
+    assert_eq!("МОВ-Л1Д".parse::<Route>(), Err(ParseError::InvalidLetter { ch: '1', index: 5 }));
+    assert_eq!("МОВ+ЛЕД".parse::<Route>(), Err(ParseError::InvalidLength { got: 7, expected: 6 }));
+    assert_eq!("  ".parse::<Route>(), Err(ParseError::Empty));
+    assert_eq!("МОВЛЕ".parse::<Route>(), Err(ParseError::InvalidLength { got: 5, expected: 6 }));
+    assert_eq!("МОВЛ1Д".parse::<Route>(), Err(ParseError::InvalidLetter { ch: '1', index: 4 }));
+    assert_eq!("М1ВЛЕД".parse::<Route>(), Err(ParseError::InvalidLetter { ch: '1', index: 1 }));
+}
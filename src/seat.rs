@@ -0,0 +1,101 @@
+//! Seat number, row followed by the seat letter, e.g. 12А
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::{is_letter_or_latin, normalize};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct SeatNumber {
+    row: u8,
+    letter: char,
+}
+
+impl SeatNumber {
+    pub fn row(&self) -> u8 {
+        self.row
+    }
+
+    /// Seat letter, Cyrillic or Latin, uppercased
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SeatNumberParseError {
+    MissingRow,
+    MissingLetter,
+    InvalidDigit(char),
+    InvalidLetter(char),
+    RowOutOfRange,
+}
+
+impl fmt::Display for SeatNumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SeatNumberParseError::MissingRow => write!(f, "missing row number"),
+            SeatNumberParseError::MissingLetter => write!(f, "missing seat letter"),
+            SeatNumberParseError::InvalidDigit(c) => write!(f, "invalid character {}, expected [0-9]", c),
+            SeatNumberParseError::InvalidLetter(c) => write!(f, "invalid character {}, expected [А-ЯA-Z]", c),
+            SeatNumberParseError::RowOutOfRange => write!(f, "row number out of range, expected 1 to 255"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SeatNumberParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl FromStr for SeatNumber {
+    type Err = SeatNumberParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let letter = value.chars().next_back().ok_or(SeatNumberParseError::MissingRow)?;
+        let row = &value[..value.len() - letter.len_utf8()];
+        let letter = normalize(letter).to_ascii_uppercase();
+        if letter.is_ascii_digit() {
+            return Err(SeatNumberParseError::MissingLetter);
+        }
+        if !is_letter_or_latin(letter) {
+            return Err(SeatNumberParseError::InvalidLetter(letter));
+        }
+        if row.is_empty() {
+            return Err(SeatNumberParseError::MissingRow);
+        }
+        if let Some(c) = row.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(SeatNumberParseError::InvalidDigit(c));
+        }
+        match row.parse() {
+            Ok(0) | Err(_) => Err(SeatNumberParseError::RowOutOfRange),
+            Ok(row) => Ok(SeatNumber { row, letter }),
+        }
+    }
+}
+
+impl fmt::Display for SeatNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.row, self.letter)
+    }
+}
+
+#[test]
+fn test_seat_number() {
+    let seat: SeatNumber = "12а".parse().unwrap();
+    assert_eq!(seat.row(), 12);
+    assert_eq!(seat.letter(), 'А');
+    assert_eq!(seat.to_string(), "12А");
+    assert_eq!("3f".parse::<SeatNumber>().unwrap().to_string(), "3F");
+    assert_eq!("А".parse::<SeatNumber>(), Err(SeatNumberParseError::MissingRow));
+    assert_eq!("12".parse::<SeatNumber>(), Err(SeatNumberParseError::MissingLetter));
+    assert_eq!("12!".parse::<SeatNumber>(), Err(SeatNumberParseError::InvalidLetter('!')));
+    assert_eq!("1Б2А".parse::<SeatNumber>(), Err(SeatNumberParseError::InvalidDigit('Б')));
+    assert_eq!("256А".parse::<SeatNumber>(), Err(SeatNumberParseError::RowOutOfRange));
+    assert_eq!("0А".parse::<SeatNumber>(), Err(SeatNumberParseError::RowOutOfRange));
+}
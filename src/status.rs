@@ -0,0 +1,141 @@
+//! Segment status codes
+//!
+//! Eight AIRIMP action/advice/status codes are recognized as variants: HK,
+//! HL, KK, TK, UN, UC, XX and NO. AIRIMP, the ATA/IATA Reservations
+//! Interline Message Procedures - Passenger, is the source for the Latin
+//! forms and their meanings. It has many more, like NN, SS, HX, UU, WK, WL
+//! or GK; those parse as `Other`.
+//!
+//! There is no published table of the Cyrillic forms to cite. They are the
+//! letter for letter equivalents Sirena displays (НК for HK), mapping H, K, L,
+//! T, U, C, X, N and O to Н, К, Л, Т, У, С, Х, Н and О. H and N share Н, so
+//! codes that differ only there, like HN and NN, can't both have a Cyrillic
+//! form. No recognized pair collides; for `Other` Н is H. Both forms are
+//! accepted in either case, and the Cyrillic one is what `Display` produces.
+
+use core::fmt;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+
+use super::{is_letter_or_latin, CyrillicCode, ParseError};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum SegmentStatus {
+    /// НК, HK: holds confirmed
+    Confirmed,
+    /// НЛ, HL: holds waitlisted
+    Waitlisted,
+    /// КК, KK: confirmed by the carrier
+    CarrierConfirmed,
+    /// ТК, TK: confirmed with a schedule change
+    ScheduleChanged,
+    /// УН, UN: unable, flight does not operate
+    Unable,
+    /// УС, UC: unable to accept, not waitlisted
+    UnableToConfirm,
+    /// ХХ, XX: cancelled
+    Cancelled,
+    /// НО, NO: no action taken
+    NoAction,
+    /// Any other valid code, as KOI8-R bytes. Latin codes are stored in
+    /// Cyrillic when all their letters have a twin, see `cyrillic_twin`
+    Other([u8; 2]),
+}
+
+/// Cyrillic and AIRIMP forms of the recognized statuses
+static STATUSES: &[(SegmentStatus, &str, &str)] = &[
+    (SegmentStatus::Confirmed, "НК", "HK"),
+    (SegmentStatus::Waitlisted, "НЛ", "HL"),
+    (SegmentStatus::CarrierConfirmed, "КК", "KK"),
+    (SegmentStatus::ScheduleChanged, "ТК", "TK"),
+    (SegmentStatus::Unable, "УН", "UN"),
+    (SegmentStatus::UnableToConfirm, "УС", "UC"),
+    (SegmentStatus::Cancelled, "ХХ", "XX"),
+    (SegmentStatus::NoAction, "НО", "NO"),
+];
+
+impl SegmentStatus {
+    pub fn as_str(&self) -> Cow<'static, str> {
+        match *self {
+            SegmentStatus::Other(bytes) => Cow::Owned(CyrillicCode::new(bytes).as_str().into_owned()),
+            status => Cow::Borrowed(STATUSES.iter().find(|s| s.0 == status).map_or("", |s| s.1)),
+        }
+    }
+
+    /// AIRIMP form of a recognized status, like HK
+    pub fn to_latin(&self) -> Option<&'static str> {
+        STATUSES.iter().find(|s| s.0 == *self).map(|s| s.2)
+    }
+}
+
+/// Cyrillic letter the recognized statuses use for a Latin one. N is left
+/// out, as Н already stands for H: an unrecognized Latin code with an N
+/// keeps its Latin form rather than colliding with the one with an H
+fn cyrillic_twin(c: char) -> Option<char> {
+    Some(match c {
+        'H' => 'Н', 'K' => 'К', 'L' => 'Л', 'T' => 'Т', 'U' => 'У', 'C' => 'С', 'X' => 'Х', 'O' => 'О',
+        _ => return None,
+    })
+}
+
+impl FromStr for SegmentStatus {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // lowercase Cyrillic is normalized by `parse`, Latin isn't
+        let value: String = value.chars().map(|c| c.to_ascii_uppercase()).collect();
+        let code = CyrillicCode::<2>::parse(&value, is_letter_or_latin)?;
+        let decoded = code.as_str();
+        let latin = decoded.starts_with(|c: char| c.is_ascii());
+        if let Some((index, ch)) = decoded.chars().enumerate().find(|&(_, c)| c.is_ascii() != latin) {
+            // like НK, Cyrillic Н and Latin K
+            return Err(ParseError::InvalidLetter { ch, index });
+        }
+        if let Some(status) = STATUSES.iter().find(|s| s.1 == decoded || s.2 == decoded) {
+            return Ok(status.0);
+        }
+        let twins: Option<String> = if latin { decoded.chars().map(cyrillic_twin).collect() } else { None };
+        match twins {
+            Some(cyrillic) => CyrillicCode::<2>::encode(&cyrillic).map(SegmentStatus::Other),
+            None => Ok(SegmentStatus::Other(code.0)),
+        }
+    }
+}
+
+impl fmt::Display for SegmentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.as_str())
+    }
+}
+
+gen_str_serde!(SegmentStatus, "a segment status code");
+gen_str_schema!(SegmentStatus, 2, 2, "^([А-Я]{2}|[A-Z]{2})$");
+
+#[test]
+fn test_segment_status() {
+    use serde_json;
+
+    assert_eq!("НК".parse::<SegmentStatus>().unwrap(), SegmentStatus::Confirmed);
+    assert_eq!("HK".parse::<SegmentStatus>().unwrap(), SegmentStatus::Confirmed);
+    assert_eq!("хх".parse::<SegmentStatus>().unwrap(), SegmentStatus::Cancelled);
+    assert_eq!("hk".parse::<SegmentStatus>().unwrap(), SegmentStatus::Confirmed);
+    assert_eq!("Uc".parse::<SegmentStatus>().unwrap(), SegmentStatus::UnableToConfirm);
+    assert_eq!(SegmentStatus::Waitlisted.to_string(), "НЛ");
+    assert_eq!(SegmentStatus::Waitlisted.to_latin(), Some("HL"));
+    let other: SegmentStatus = "ЗЗ".parse().unwrap();
+    assert_eq!(other.to_string(), "ЗЗ");
+    assert_eq!(other.to_latin(), None);
+    assert!("Н1".parse::<SegmentStatus>().is_err());
+    assert_eq!("НK".parse::<SegmentStatus>(), Err(ParseError::InvalidLetter { ch: 'K', index: 1 }));
+    assert_eq!("hК".parse::<SegmentStatus>(), Err(ParseError::InvalidLetter { ch: 'К', index: 1 }));
+    // unrecognized codes are the same in either script
+    assert_eq!("HX".parse::<SegmentStatus>().unwrap(), "НХ".parse::<SegmentStatus>().unwrap());
+    assert_eq!("hx".parse::<SegmentStatus>().unwrap().to_string(), "НХ");
+    assert_eq!("NN".parse::<SegmentStatus>().unwrap().to_string(), "NN");
+    assert_eq!("WK".parse::<SegmentStatus>().unwrap().to_string(), "WK");
+    assert_eq!(serde_json::to_string(&SegmentStatus::Confirmed).unwrap(), "\"НК\"");
+    assert_eq!(serde_json::from_str::<SegmentStatus>("\"TK\"").unwrap(), SegmentStatus::ScheduleChanged);
+}
@@ -0,0 +1,153 @@
+//! Static tables of well-known codes
+//!
+//! Tables are sorted by their first column, so they can be binary searched.
+//! They only cover well-known codes and are far from complete.
+
+use super::{encode_literal, AirportCode};
+
+/// Latin IATA equivalents of airport codes
+pub static LATIN_AIRPORTS: &[(&str, &str)] = &[
+    ("ВВО", "VVO"),
+    ("ВНК", "VKO"),
+    ("ДМД", "DME"),
+    ("ЖКВ", "ZIA"),
+    ("ИКТ", "IKT"),
+    ("КЗН", "KZN"),
+    ("КЛЦ", "SVX"),
+    ("КРР", "KRR"),
+    ("ОМС", "OMS"),
+    ("ПЛК", "LED"),
+    ("РОВ", "ROV"),
+    ("СОЧ", "AER"),
+    ("ТЛЧ", "OVB"),
+    ("УФА", "UFA"),
+    ("ХБР", "KHV"),
+    ("ШРМ", "SVO"),
+];
+
+/// Latin IATA equivalents of city codes
+pub static LATIN_CITIES: &[(&str, &str)] = &[
+    ("ВВО", "VVO"),
+    ("ЕКБ", "SVX"),
+    ("ИКТ", "IKT"),
+    ("КЗН", "KZN"),
+    ("КРР", "KRR"),
+    ("МОВ", "MOW"),
+    ("ОВБ", "OVB"),
+    ("ОМС", "OMS"),
+    ("РОВ", "ROV"),
+    ("СОЧ", "AER"),
+    ("СПТ", "LED"),
+    ("УФА", "UFA"),
+    ("ХБР", "KHV"),
+];
+
+/// City each airport belongs to
+pub static AIRPORT_CITIES: &[(&str, &str)] = &[
+    ("ВВО", "ВВО"),
+    ("ВНК", "МОВ"),
+    ("ДМД", "МОВ"),
+    ("ЖКВ", "МОВ"),
+    ("ИКТ", "ИКТ"),
+    ("КЗН", "КЗН"),
+    ("КЛЦ", "ЕКБ"),
+    ("КРР", "КРР"),
+    ("ОМС", "ОМС"),
+    ("ПЛК", "СПТ"),
+    ("РОВ", "РОВ"),
+    ("СОЧ", "СОЧ"),
+    ("ТЛЧ", "ОВБ"),
+    ("УФА", "УФА"),
+    ("ХБР", "ХБР"),
+    ("ШРМ", "МОВ"),
+];
+
+/// Human-readable names of the major Russian and CIS airports
+#[cfg(feature = "airport-names")]
+pub static AIRPORT_NAMES: &[(&str, &str)] = &[
+    ("АЛА", "Алматы"),
+    ("АСТ", "Астана"),
+    ("БАК", "Баку Гейдар Алиев"),
+    ("БИШ", "Бишкек Манас"),
+    ("ВВО", "Владивосток Кневичи"),
+    ("ВНК", "Москва Внуково"),
+    ("ДМД", "Москва Домодедово"),
+    ("ЕВН", "Ереван Звартноц"),
+    ("ЖКВ", "Москва Жуковский"),
+    ("ИКТ", "Иркутск"),
+    ("КЗН", "Казань"),
+    ("КЛД", "Калининград Храброво"),
+    ("КЛЦ", "Екатеринбург Кольцово"),
+    ("КРР", "Краснодар Пашковский"),
+    ("КУФ", "Самара Курумоч"),
+    ("КЯА", "Красноярск Емельяново"),
+    ("МНК", "Минск Национальный"),
+    ("МРВ", "Минеральные Воды"),
+    ("НЖС", "Нижний Новгород Стригино"),
+    ("ОМС", "Омск Центральный"),
+    ("ПЕЕ", "Пермь Большое Савино"),
+    ("ПЛК", "Санкт-Петербург Пулково"),
+    ("РОВ", "Ростов-на-Дону Платов"),
+    ("СОЧ", "Сочи"),
+    ("ТЛЧ", "Новосибирск Толмачёво"),
+    ("ТШК", "Ташкент Ислам Каримов"),
+    ("УФА", "Уфа"),
+    ("ХБР", "Хабаровск Новый"),
+    ("ЧЛБ", "Челябинск Баландино"),
+    ("ШРМ", "Москва Шереметьево"),
+];
+
+/// Airports of each city, the reverse of `AIRPORT_CITIES`
+pub static CITY_AIRPORTS: &[(&str, &[AirportCode])] = &[
+    ("ВВО", &[airport("ВВО")]),
+    ("ЕКБ", &[airport("КЛЦ")]),
+    ("ИКТ", &[airport("ИКТ")]),
+    ("КЗН", &[airport("КЗН")]),
+    ("КРР", &[airport("КРР")]),
+    ("МОВ", &[airport("ВНК"), airport("ДМД"), airport("ЖКВ"), airport("ШРМ")]),
+    ("ОВБ", &[airport("ТЛЧ")]),
+    ("ОМС", &[airport("ОМС")]),
+    ("РОВ", &[airport("РОВ")]),
+    ("СОЧ", &[airport("СОЧ")]),
+    ("СПТ", &[airport("ПЛК")]),
+    ("УФА", &[airport("УФА")]),
+    ("ХБР", &[airport("ХБР")]),
+];
+
+const fn airport(code: &str) -> AirportCode {
+    AirportCode::from_bytes_array(encode_literal(code, 0, false))
+}
+
+pub fn lookup(table: &'static [(&'static str, &'static str)], code: &str) -> Option<&'static str> {
+    table.binary_search_by(|&(key, _)| key.cmp(code))
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Tables are sorted by the first column, so going back is a linear scan
+pub fn reverse_lookup(table: &'static [(&'static str, &'static str)], code: &str) -> Option<&'static str> {
+    table.iter()
+        .find(|&&(_, value)| value.eq_ignore_ascii_case(code))
+        .map(|&(key, _)| key)
+}
+
+#[test]
+fn test_tables_sorted() {
+    for table in &[LATIN_AIRPORTS, LATIN_CITIES, AIRPORT_CITIES] {
+        assert!(table.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+    assert!(CITY_AIRPORTS.windows(2).all(|w| w[0].0 < w[1].0));
+    #[cfg(feature = "airport-names")]
+    assert!(AIRPORT_NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+#[test]
+fn test_city_airports_consistent() {
+    for &(city, airports) in CITY_AIRPORTS {
+        for airport in airports {
+            assert_eq!(lookup(AIRPORT_CITIES, &airport.as_str()), Some(city));
+        }
+    }
+    let total: usize = CITY_AIRPORTS.iter().map(|&(_, airports)| airports.len()).sum();
+    assert_eq!(total, AIRPORT_CITIES.len());
+}
@@ -0,0 +1,104 @@
+//! 13 digit ticket number: 3 digit airline accounting prefix and 10 digit
+//! serial, the serial's last digit being a check digit
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(test)]
+use alloc::string::ToString;
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct TicketNumber {
+    prefix: u16,
+    serial: u64,
+}
+
+impl TicketNumber {
+    pub fn airline_prefix(&self) -> u16 {
+        self.prefix
+    }
+
+    /// Serial number, including the trailing check digit
+    pub fn serial(&self) -> u64 {
+        self.serial
+    }
+
+    /// Check digit is the remainder of the preceding nine serial digits divided by 7.
+    /// Always true for ticket numbers obtained through `FromStr`.
+    pub fn is_valid_check_digit(&self) -> bool {
+        check_digit(self.serial / 10) == (self.serial % 10) as u8
+    }
+}
+
+fn check_digit(serial: u64) -> u8 {
+    (serial % 7) as u8
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TicketNumberParseError {
+    InvalidLength(usize),
+    InvalidDigit(char),
+    InvalidCheckDigit { expected: u8, got: u8 },
+}
+
+impl fmt::Display for TicketNumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TicketNumberParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 13", len),
+            TicketNumberParseError::InvalidDigit(c) => write!(f, "invalid character {}, expected [0-9]", c),
+            TicketNumberParseError::InvalidCheckDigit { expected, got } =>
+                write!(f, "invalid check digit {}, expected {}", got, expected),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TicketNumberParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl FromStr for TicketNumber {
+    type Err = TicketNumberParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(c) = value.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(TicketNumberParseError::InvalidDigit(c));
+        }
+        if value.len() != 13 {
+            return Err(TicketNumberParseError::InvalidLength(value.len()));
+        }
+        let (prefix, serial) = value.split_at(3);
+        let ticket = TicketNumber {
+            prefix: prefix.parse().unwrap(),
+            serial: serial.parse().unwrap(),
+        };
+        if !ticket.is_valid_check_digit() {
+            return Err(TicketNumberParseError::InvalidCheckDigit {
+                expected: check_digit(ticket.serial / 10),
+                got: (ticket.serial % 10) as u8,
+            });
+        }
+        Ok(ticket)
+    }
+}
+
+impl fmt::Display for TicketNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:03}{:010}", self.prefix, self.serial)
+    }
+}
+
+#[test]
+fn test_ticket_number() {
+    let ticket: TicketNumber = "5550012345675".parse().unwrap();
+    assert_eq!(ticket.airline_prefix(), 555);
+    assert_eq!(ticket.serial(), 12345675);
+    assert!(ticket.is_valid_check_digit());
+    assert_eq!(ticket.to_string(), "5550012345675");
+    assert_eq!("5550012345674".parse::<TicketNumber>(),
+               Err(TicketNumberParseError::InvalidCheckDigit { expected: 5, got: 4 }));
+    assert_eq!("555001234567".parse::<TicketNumber>(), Err(TicketNumberParseError::InvalidLength(12)));
+    assert_eq!("555-012345674".parse::<TicketNumber>(), Err(TicketNumberParseError::InvalidDigit('-')));
+}
@@ -0,0 +1,109 @@
+//! Time of day as four HHMM digits, e.g. 0745
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(test)]
+use alloc::string::ToString;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveTime;
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct SirenaTime {
+    hour: u8,
+    minute: u8,
+}
+
+impl SirenaTime {
+    /// Hour, 0 to 23
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Minute, 0 to 59
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_naive_time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.hour.into(), self.minute.into(), 0)
+            .expect("hour and minute are validated")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SirenaTimeParseError {
+    InvalidLength(usize),
+    InvalidDigit(char),
+    HourOutOfRange(u8),
+    MinuteOutOfRange(u8),
+}
+
+impl fmt::Display for SirenaTimeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SirenaTimeParseError::InvalidLength(len) => write!(f, "invalid length {}, expected 4", len),
+            SirenaTimeParseError::InvalidDigit(c) => write!(f, "invalid character {}, expected [0-9]", c),
+            SirenaTimeParseError::HourOutOfRange(hour) => write!(f, "hour {} out of range, expected 0 to 23", hour),
+            SirenaTimeParseError::MinuteOutOfRange(minute) =>
+                write!(f, "minute {} out of range, expected 0 to 59", minute),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SirenaTimeParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl FromStr for SirenaTime {
+    type Err = SirenaTimeParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(c) = value.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(SirenaTimeParseError::InvalidDigit(c));
+        }
+        if value.len() != 4 {
+            return Err(SirenaTimeParseError::InvalidLength(value.len()));
+        }
+        let digits = value.as_bytes();
+        let hour = (digits[0] - b'0') * 10 + digits[1] - b'0';
+        let minute = (digits[2] - b'0') * 10 + digits[3] - b'0';
+        if hour > 23 {
+            return Err(SirenaTimeParseError::HourOutOfRange(hour));
+        }
+        if minute > 59 {
+            return Err(SirenaTimeParseError::MinuteOutOfRange(minute));
+        }
+        Ok(SirenaTime { hour, minute })
+    }
+}
+
+impl fmt::Display for SirenaTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}{:02}", self.hour, self.minute)
+    }
+}
+
+#[test]
+fn test_sirena_time() {
+    let time: SirenaTime = "0745".parse().unwrap();
+    assert_eq!((time.hour(), time.minute()), (7, 45));
+    assert_eq!(time.to_string(), "0745");
+    assert_eq!("0000".parse::<SirenaTime>().unwrap().to_string(), "0000");
+    assert_eq!("2400".parse::<SirenaTime>(), Err(SirenaTimeParseError::HourOutOfRange(24)));
+    assert_eq!("1260".parse::<SirenaTime>(), Err(SirenaTimeParseError::MinuteOutOfRange(60)));
+    assert_eq!("745".parse::<SirenaTime>(), Err(SirenaTimeParseError::InvalidLength(3)));
+    assert_eq!("07:45".parse::<SirenaTime>(), Err(SirenaTimeParseError::InvalidDigit(':')));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_sirena_time_chrono() {
+    let time: SirenaTime = "2359".parse().unwrap();
+    assert_eq!(time.to_naive_time(), NaiveTime::from_hms_opt(23, 59, 0).unwrap());
+}